@@ -0,0 +1,567 @@
+// Traversals and shortest paths over `mygraph::Graph`. Every search returns
+// its distances together with a predecessor map, so paths can be rebuilt.
+
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+
+use crate::indexed_heap::IndexedHeap;
+use crate::mygraph::{Graph, NodeId};
+use crate::project_errors::CollectionError;
+
+/// Edge weights that add up to path lengths; `Default` must be zero.
+///
+/// Implemented for the primitive integers, where a path length that does not
+/// fit fails the search with `WeightOverflow`, and for `f32` and `f64`. NaN
+/// weights give unspecified results.
+pub trait Weight: Copy + PartialOrd + Default {
+    /// `self + other`, or `None` if the sum does not fit.
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! integer_weight {
+    ($($t:ty),*) => {
+        $(impl Weight for $t {
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+        })*
+    };
+}
+
+macro_rules! float_weight {
+    ($($t:ty),*) => {
+        $(impl Weight for $t {
+            fn checked_add(self, other: Self) -> Option<Self> {
+                Some(self + other)
+            }
+        })*
+    };
+}
+
+integer_weight!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+float_weight!(f32, f64);
+
+fn add<W: Weight>(a: W, b: W, operation: &'static str) -> Result<W, CollectionError> {
+    a.checked_add(b).ok_or(CollectionError::WeightOverflow { operation })
+}
+
+// Orders a weight for `IndexedHeap`, treating incomparable weights as equal.
+#[derive(PartialEq)]
+struct Priority<W>(W);
+
+impl<W: PartialOrd> Eq for Priority<W> {}
+
+impl<W: PartialOrd> PartialOrd for Priority<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: PartialOrd> Ord for Priority<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.partial_cmp(&other.0).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Distances from one source node, with the predecessor of every reached
+/// node on its path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPaths<W> {
+    source: NodeId,
+    distances: HashMap<NodeId, W>,
+    predecessors: HashMap<NodeId, NodeId>,
+}
+
+impl<W: Copy> ShortestPaths<W> {
+    fn new(source: NodeId, zero: W) -> Self {
+        Self {
+            source,
+            distances: HashMap::from([(source, zero)]),
+            predecessors: HashMap::new(),
+        }
+    }
+
+    pub fn source(&self) -> NodeId {
+        self.source
+    }
+
+    /// The distance to `node`, or `None` if it was not reached.
+    pub fn distance(&self, node: NodeId) -> Option<W> {
+        self.distances.get(&node).copied()
+    }
+
+    pub fn predecessor(&self, node: NodeId) -> Option<NodeId> {
+        self.predecessors.get(&node).copied()
+    }
+
+    pub fn distances(&self) -> &HashMap<NodeId, W> {
+        &self.distances
+    }
+
+    pub fn predecessors(&self) -> &HashMap<NodeId, NodeId> {
+        &self.predecessors
+    }
+
+    /// The nodes on the path from the source to `node`, both included.
+    pub fn path_to(&self, node: NodeId) -> Option<Vec<NodeId>> {
+        self.distances.contains_key(&node).then(|| walk_back(&self.predecessors, node))
+    }
+}
+
+/// What an iterative depth-first search reports as it goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DfsEvent {
+    /// The node was reached for the first time.
+    Discover(NodeId),
+    /// Everything reachable from the node has been explored.
+    Finish(NodeId),
+}
+
+/// The result of `dfs`: discovery and finish times from one shared clock,
+/// and the predecessor of every node in the depth-first tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepthFirst {
+    source: NodeId,
+    discovered: HashMap<NodeId, usize>,
+    finished: HashMap<NodeId, usize>,
+    predecessors: HashMap<NodeId, NodeId>,
+}
+
+impl DepthFirst {
+    pub fn source(&self) -> NodeId {
+        self.source
+    }
+
+    pub fn discovery_time(&self, node: NodeId) -> Option<usize> {
+        self.discovered.get(&node).copied()
+    }
+
+    pub fn finish_time(&self, node: NodeId) -> Option<usize> {
+        self.finished.get(&node).copied()
+    }
+
+    pub fn predecessor(&self, node: NodeId) -> Option<NodeId> {
+        self.predecessors.get(&node).copied()
+    }
+
+    pub fn predecessors(&self) -> &HashMap<NodeId, NodeId> {
+        &self.predecessors
+    }
+
+    /// The path from the source to `node` in the depth-first tree.
+    pub fn path_to(&self, node: NodeId) -> Option<Vec<NodeId>> {
+        self.discovered.contains_key(&node).then(|| walk_back(&self.predecessors, node))
+    }
+}
+
+/// Distances between every pair of nodes, from `floyd_warshall`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllPairsPaths<W> {
+    // indexed by `NodeId::index`, `[from][to]`
+    distances: Vec<Vec<Option<W>>>,
+    predecessors: Vec<Vec<Option<NodeId>>>,
+}
+
+impl<W: Copy> AllPairsPaths<W> {
+    /// The distance from `from` to `to`, or `None` if there is no path.
+    pub fn distance(&self, from: NodeId, to: NodeId) -> Option<W> {
+        *self.distances.get(from.index())?.get(to.index())?
+    }
+
+    /// The node before `to` on the path from `from`.
+    pub fn predecessor(&self, from: NodeId, to: NodeId) -> Option<NodeId> {
+        *self.predecessors.get(from.index())?.get(to.index())?
+    }
+
+    pub fn path(&self, from: NodeId, to: NodeId) -> Option<Vec<NodeId>> {
+        self.distance(from, to)?;
+        let mut path = vec![to];
+        let mut node = to;
+        while node != from {
+            node = self.predecessor(from, node)?;
+            path.push(node);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+fn walk_back(predecessors: &HashMap<NodeId, NodeId>, mut node: NodeId) -> Vec<NodeId> {
+    let mut path = vec![node];
+    while let Some(&previous) = predecessors.get(&node) {
+        path.push(previous);
+        node = previous;
+    }
+    path.reverse();
+    path
+}
+
+fn check_node<N, E>(graph: &Graph<N, E>, node: NodeId, operation: &'static str) -> Result<(), CollectionError> {
+    if graph.contains_node(node) {
+        Ok(())
+    } else {
+        Err(CollectionError::InvalidHandle { operation })
+    }
+}
+
+/// Breadth-first search; the distances count edges.
+pub fn bfs<N, E>(graph: &Graph<N, E>, source: NodeId) -> Result<ShortestPaths<usize>, CollectionError> {
+    check_node(graph, source, "bfs")?;
+    let mut paths = ShortestPaths::new(source, 0);
+    let mut queue = VecDeque::from([source]);
+    while let Some(node) = queue.pop_front() {
+        let next = paths.distances[&node] + 1;
+        for edge in graph.neighbors(node) {
+            if let Entry::Vacant(slot) = paths.distances.entry(edge.to) {
+                slot.insert(next);
+                paths.predecessors.insert(edge.to, node);
+                queue.push_back(edge.to);
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// Depth-first search from `source` with an explicit stack, so deep graphs
+/// cannot overflow the call stack. `visit` sees every event in order.
+pub fn dfs<N, E, F>(graph: &Graph<N, E>, source: NodeId, mut visit: F) -> Result<DepthFirst, CollectionError>
+where
+    F: FnMut(DfsEvent),
+{
+    check_node(graph, source, "dfs")?;
+    let mut search = DepthFirst {
+        source,
+        discovered: HashMap::from([(source, 0)]),
+        finished: HashMap::new(),
+        predecessors: HashMap::new(),
+    };
+    let mut clock = 1;
+    visit(DfsEvent::Discover(source));
+
+    // every entry keeps its place in the node's adjacency table
+    let mut stack = vec![(source, graph.neighbors(source))];
+    while let Some((node, edges)) = stack.last_mut() {
+        let node = *node;
+        match edges.find(|edge| !search.discovered.contains_key(&edge.to)) {
+            Some(edge) => {
+                search.discovered.insert(edge.to, clock);
+                search.predecessors.insert(edge.to, node);
+                visit(DfsEvent::Discover(edge.to));
+                stack.push((edge.to, graph.neighbors(edge.to)));
+            }
+            None => {
+                stack.pop();
+                search.finished.insert(node, clock);
+                visit(DfsEvent::Finish(node));
+            }
+        }
+        clock += 1;
+    }
+    Ok(search)
+}
+
+/// Dijkstra's algorithm. Fails if it meets a negative edge weight.
+pub fn dijkstra<N, E: Weight>(graph: &Graph<N, E>, source: NodeId) -> Result<ShortestPaths<E>, CollectionError> {
+    best_first(graph, source, None, |_| E::default(), "dijkstra")
+}
+
+/// A* search from `source` to `target`. `heuristic` estimates the remaining
+/// distance to `target` and must never overestimate it.
+///
+/// The search stops once `target` is settled, so only the distance and path
+/// to `target` are final; other reached nodes may hold longer distances.
+pub fn astar<N, E, H>(
+    graph: &Graph<N, E>,
+    source: NodeId,
+    target: NodeId,
+    heuristic: H,
+) -> Result<ShortestPaths<E>, CollectionError>
+where
+    E: Weight,
+    H: FnMut(NodeId) -> E,
+{
+    check_node(graph, target, "astar")?;
+    best_first(graph, source, Some(target), heuristic, "astar")
+}
+
+// Dijkstra ordered by distance plus `heuristic`. A node is queued again when
+// a shorter path to it turns up, so an inconsistent heuristic stays correct.
+fn best_first<N, E, H>(
+    graph: &Graph<N, E>,
+    source: NodeId,
+    target: Option<NodeId>,
+    mut heuristic: H,
+    operation: &'static str,
+) -> Result<ShortestPaths<E>, CollectionError>
+where
+    E: Weight,
+    H: FnMut(NodeId) -> E,
+{
+    check_node(graph, source, operation)?;
+    let zero = E::default();
+    let mut paths = ShortestPaths::new(source, zero);
+    let mut open = IndexedHeap::new();
+    open.push(source, Priority(heuristic(source)));
+    while let Some((node, _)) = open.pop() {
+        if Some(node) == target {
+            break;
+        }
+        let distance = paths.distances[&node];
+        for edge in graph.neighbors(node) {
+            if edge.weight < zero {
+                return Err(CollectionError::NegativeWeight { operation });
+            }
+            let candidate = add(distance, edge.weight, operation)?;
+            if paths.distances.get(&edge.to).is_none_or(|&known| candidate < known) {
+                paths.distances.insert(edge.to, candidate);
+                paths.predecessors.insert(edge.to, node);
+                open.push(edge.to, Priority(add(candidate, heuristic(edge.to), operation)?));
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// Bellman-Ford, which allows negative weights. Fails if a negative cycle
+/// is reachable from `source`.
+pub fn bellman_ford<N, E: Weight>(graph: &Graph<N, E>, source: NodeId) -> Result<ShortestPaths<E>, CollectionError> {
+    check_node(graph, source, "bellman_ford")?;
+    let mut paths = ShortestPaths::new(source, E::default());
+    let nodes: Vec<NodeId> = graph.node_ids().collect();
+    // shortest paths have fewer edges than there are nodes, so distances that
+    // still shrink in the last round come from a cycle
+    for _ in 0..nodes.len() {
+        let mut changed = false;
+        for &node in &nodes {
+            let Some(&distance) = paths.distances.get(&node) else {
+                continue;
+            };
+            for edge in graph.neighbors(node) {
+                let candidate = add(distance, edge.weight, "bellman_ford")?;
+                if paths.distances.get(&edge.to).is_none_or(|&known| candidate < known) {
+                    paths.distances.insert(edge.to, candidate);
+                    paths.predecessors.insert(edge.to, node);
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            return Ok(paths);
+        }
+    }
+    Err(CollectionError::NegativeCycle { operation: "bellman_ford" })
+}
+
+/// Floyd-Warshall over all pairs of nodes, in O(V^3) time and O(V^2)
+/// memory. Fails if the graph has a negative cycle.
+pub fn floyd_warshall<N, E: Weight>(graph: &Graph<N, E>) -> Result<AllPairsPaths<E>, CollectionError> {
+    let n = graph.node_bound();
+    let zero = E::default();
+    let mut distances = vec![vec![None; n]; n];
+    let mut predecessors = vec![vec![None; n]; n];
+    for from in graph.node_ids() {
+        distances[from.index()][from.index()] = Some(zero);
+        for edge in graph.neighbors(from) {
+            let cell = &mut distances[from.index()][edge.to.index()];
+            if cell.is_none_or(|known| edge.weight < known) {
+                *cell = Some(edge.weight);
+                predecessors[from.index()][edge.to.index()] = Some(from);
+            }
+        }
+    }
+
+    for k in 0..n {
+        for i in 0..n {
+            let Some(to_k) = distances[i][k] else {
+                continue;
+            };
+            for j in 0..n {
+                let Some(from_k) = distances[k][j] else {
+                    continue;
+                };
+                let candidate = add(to_k, from_k, "floyd_warshall")?;
+                if distances[i][j].is_none_or(|known| candidate < known) {
+                    distances[i][j] = Some(candidate);
+                    predecessors[i][j] = predecessors[k][j];
+                }
+            }
+        }
+    }
+
+    if (0..n).any(|i| distances[i][i].is_some_and(|d| d < zero)) {
+        return Err(CollectionError::NegativeCycle { operation: "floyd_warshall" });
+    }
+    Ok(AllPairsPaths { distances, predecessors })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{astar, bellman_ford, bfs, dfs, dijkstra, floyd_warshall, DfsEvent};
+    use crate::mygraph::{Graph, GraphKind, NodeId};
+    use crate::project_errors::CollectionError;
+
+    fn graph_of(kind: GraphKind, nodes: usize, edges: &[(usize, usize, i32)]) -> (Graph<(), i32>, Vec<NodeId>) {
+        let mut graph = Graph::new(kind);
+        let ids: Vec<NodeId> = (0..nodes).map(|_| graph.add_node(())).collect();
+        for &(from, to, weight) in edges {
+            graph.add_edge(ids[from], ids[to], weight).unwrap();
+        }
+        (graph, ids)
+    }
+
+    // s, t, x, y, z with the non-negative weights from CLRS 24.3.
+    fn clrs_dijkstra() -> (Graph<(), i32>, Vec<NodeId>) {
+        let edges = [
+            (0, 1, 10), (0, 3, 5), (1, 2, 1), (1, 3, 2), (3, 1, 3),
+            (3, 2, 9), (3, 4, 2), (2, 4, 4), (4, 2, 6), (4, 0, 7),
+        ];
+        graph_of(GraphKind::Directed, 5, &edges)
+    }
+
+    #[test]
+    fn traversals_record_order_and_predecessors() {
+        let (graph, n) = graph_of(GraphKind::Directed, 5, &[(0, 1, 1), (0, 2, 1), (1, 3, 1), (2, 3, 1)]);
+        let levels = bfs(&graph, n[0]).unwrap();
+        let hops: Vec<_> = n.iter().map(|&id| levels.distance(id)).collect();
+        assert_eq!(hops, vec![Some(0), Some(1), Some(1), Some(2), None]);
+        assert_eq!(levels.path_to(n[3]), Some(vec![n[0], n[1], n[3]]));
+        assert_eq!(levels.path_to(n[4]), None);
+
+        let mut events = Vec::new();
+        let search = dfs(&graph, n[0], |event| events.push(event)).unwrap();
+        use DfsEvent::{Discover, Finish};
+        assert_eq!(
+            events,
+            vec![
+                Discover(n[0]), Discover(n[1]), Discover(n[3]), Finish(n[3]),
+                Finish(n[1]), Discover(n[2]), Finish(n[2]), Finish(n[0]),
+            ]
+        );
+        assert_eq!((search.discovery_time(n[2]), search.finish_time(n[2])), (Some(5), Some(6)));
+        assert_eq!((search.discovery_time(n[0]), search.finish_time(n[0])), (Some(0), Some(7)));
+        assert_eq!(search.predecessor(n[3]), Some(n[1]));
+        assert_eq!(search.path_to(n[2]), Some(vec![n[0], n[2]]));
+        assert_eq!(search.finish_time(n[4]), None);
+
+        let missing = n[4];
+        let mut graph = graph;
+        graph.remove_node(missing);
+        assert_eq!(bfs(&graph, missing), Err(CollectionError::InvalidHandle { operation: "bfs" }));
+        assert!(dfs(&graph, missing, |_| ()).is_err());
+    }
+
+    #[test]
+    fn dijkstra_and_astar_find_shortest_paths() {
+        let (graph, n) = clrs_dijkstra();
+        let paths = dijkstra(&graph, n[0]).unwrap();
+        let distances: Vec<_> = n.iter().map(|&id| paths.distance(id).unwrap()).collect();
+        assert_eq!(distances, vec![0, 8, 9, 5, 7]);
+        assert_eq!(paths.path_to(n[2]), Some(vec![n[0], n[3], n[1], n[2]]));
+        assert_eq!(paths.predecessor(n[4]), Some(n[3]));
+
+        // a 5x5 grid with a wall in the middle column, open at the bottom
+        let mut grid: Graph<(i32, i32), i32> = Graph::undirected();
+        let cells: Vec<NodeId> = (0..25).map(|i| grid.add_node((i % 5, i / 5))).collect();
+        for i in 0..25 {
+            if i % 5 < 4 {
+                grid.add_edge(cells[i], cells[i + 1], 1).unwrap();
+            }
+            if i < 20 {
+                grid.add_edge(cells[i], cells[i + 5], 1).unwrap();
+            }
+        }
+        for wall in [2, 7, 12, 17] {
+            grid.remove_node(cells[wall]);
+        }
+        let (start, goal) = (cells[0], cells[4]);
+        let (gx, gy) = *grid.node(goal).unwrap();
+        let manhattan = |id: NodeId| {
+            let (x, y) = *grid.node(id).unwrap();
+            (x - gx).abs() + (y - gy).abs()
+        };
+        let guided = astar(&grid, start, goal, manhattan).unwrap();
+        let plain = dijkstra(&grid, start).unwrap();
+        assert_eq!(guided.distance(goal), Some(12));
+        assert_eq!(plain.distance(goal), Some(12));
+        assert_eq!(guided.path_to(goal).unwrap().len(), 13);
+        assert!(guided.path_to(goal).unwrap().contains(&cells[22]));
+
+        let (negative, n) = graph_of(GraphKind::Directed, 2, &[(0, 1, -1)]);
+        assert_eq!(
+            dijkstra(&negative, n[0]),
+            Err(CollectionError::NegativeWeight { operation: "dijkstra" })
+        );
+    }
+
+    #[test]
+    fn negative_weights_and_cycles() {
+        // s, t, x, y, z with the weights from CLRS 24.1
+        let edges = [
+            (0, 1, 6), (0, 3, 7), (1, 2, 5), (1, 3, 8), (1, 4, -4),
+            (2, 1, -2), (3, 2, -3), (3, 4, 9), (4, 2, 7), (4, 0, 2),
+        ];
+        let (mut graph, n) = graph_of(GraphKind::Directed, 5, &edges);
+        let paths = bellman_ford(&graph, n[0]).unwrap();
+        let distances: Vec<_> = n.iter().map(|&id| paths.distance(id).unwrap()).collect();
+        assert_eq!(distances, vec![0, 2, 4, 7, -2]);
+        assert_eq!(paths.path_to(n[4]), Some(vec![n[0], n[3], n[2], n[1], n[4]]));
+
+        let all = floyd_warshall(&graph).unwrap();
+        for &from in &n {
+            let single = bellman_ford(&graph, from).unwrap();
+            for &to in &n {
+                assert_eq!(all.distance(from, to), single.distance(to));
+            }
+        }
+        assert_eq!(all.path(n[0], n[4]), Some(vec![n[0], n[3], n[2], n[1], n[4]]));
+        assert_eq!(all.path(n[2], n[2]), Some(vec![n[2]]));
+
+        graph.add_edge(n[2], n[1], -3).unwrap();
+        assert_eq!(
+            bellman_ford(&graph, n[0]),
+            Err(CollectionError::NegativeCycle { operation: "bellman_ford" })
+        );
+        assert_eq!(
+            floyd_warshall(&graph),
+            Err(CollectionError::NegativeCycle { operation: "floyd_warshall" })
+        );
+    }
+
+    #[test]
+    fn all_pairs_on_an_undirected_graph() {
+        let (mut graph, n) = graph_of(GraphKind::Undirected, 5, &[(0, 1, 4), (1, 2, 1), (0, 2, 7), (3, 4, 2)]);
+        graph.remove_node(n[3]);
+        let all = floyd_warshall(&graph).unwrap();
+        assert_eq!(all.distance(n[2], n[0]), Some(5));
+        assert_eq!(all.path(n[2], n[0]), Some(vec![n[2], n[1], n[0]]));
+        assert_eq!(all.distance(n[0], n[4]), None);
+        assert_eq!(all.distance(n[3], n[3]), None);
+        assert_eq!(dijkstra(&graph, n[0]).unwrap().distance(n[2]), Some(5));
+    }
+
+    #[test]
+    fn float_weights_and_overflow() {
+        let mut graph: Graph<(), f64> = Graph::directed();
+        let n: Vec<NodeId> = (0..3).map(|_| graph.add_node(())).collect();
+        graph.add_edge(n[0], n[1], 0.5).unwrap();
+        graph.add_edge(n[1], n[2], 0.25).unwrap();
+        graph.add_edge(n[0], n[2], 1.0).unwrap();
+        assert_eq!(dijkstra(&graph, n[0]).unwrap().distance(n[2]), Some(0.75));
+        assert_eq!(bellman_ford(&graph, n[0]).unwrap().path_to(n[2]), Some(vec![n[0], n[1], n[2]]));
+        assert_eq!(floyd_warshall(&graph).unwrap().distance(n[0], n[2]), Some(0.75));
+
+        let mut graph: Graph<(), u8> = Graph::directed();
+        let n: Vec<NodeId> = (0..3).map(|_| graph.add_node(())).collect();
+        graph.add_edge(n[0], n[1], 200).unwrap();
+        graph.add_edge(n[1], n[2], 100).unwrap();
+        assert_eq!(
+            dijkstra(&graph, n[0]),
+            Err(CollectionError::WeightOverflow { operation: "dijkstra" })
+        );
+        assert_eq!(
+            floyd_warshall(&graph),
+            Err(CollectionError::WeightOverflow { operation: "floyd_warshall" })
+        );
+    }
+}
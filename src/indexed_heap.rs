@@ -0,0 +1,291 @@
+// Min-priority queue over external keys, for algorithms that need to change
+// the priority of queued items (Dijkstra, Prim, event schedulers).
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::hash::Hash;
+use std::mem;
+
+use crate::project_errors::CollectionError;
+
+/// A binary min-heap of `(key, priority)` pairs that also maps every key to
+/// its position, so a queued key can be found, re-prioritized or removed in
+/// O(log n). Each key is queued at most once.
+pub struct IndexedHeap<K, P> {
+    heap: Vec<(K, P)>,
+    positions: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> IndexedHeap<K, P> {
+    pub fn new() -> Self {
+        Self {
+            heap: Vec::new(),
+            positions: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.positions.contains_key(key)
+    }
+
+    pub fn priority<Q>(&self, key: &Q) -> Option<&P>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.positions.get(key).map(|&pos| &self.heap[pos].1)
+    }
+
+    /// The key with the lowest priority.
+    pub fn peek(&self) -> Option<(&K, &P)> {
+        self.heap.first().map(|(key, priority)| (key, priority))
+    }
+
+    /// Queues `key`, or updates its priority if it is already queued.
+    /// Returns the old priority in that case.
+    pub fn push(&mut self, key: K, priority: P) -> Option<P> {
+        if self.positions.contains_key(&key) {
+            return self.change_priority(&key, priority);
+        }
+        let pos = self.heap.len();
+        self.positions.insert(key.clone(), pos);
+        self.heap.push((key, priority));
+        self.sift_up(pos);
+        None
+    }
+
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        Some(self.remove_at(0))
+    }
+
+    /// Sets the priority of a queued key and returns the old one, or `None`
+    /// if the key is not queued.
+    pub fn change_priority<Q>(&mut self, key: &Q, priority: P) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let pos = *self.positions.get(key)?;
+        let old = mem::replace(&mut self.heap[pos].1, priority);
+        if self.heap[pos].1 < old {
+            self.sift_up(pos);
+        } else {
+            self.sift_down(pos);
+        }
+        Some(old)
+    }
+
+    /// Lowers (or keeps) the priority of a queued key. Fails with
+    /// `InvalidHandle` if the key is not queued and with `KeyIncrease` if
+    /// `priority` is higher, leaving the heap unchanged.
+    pub fn decrease_key<Q>(&mut self, key: &Q, priority: P) -> Result<(), CollectionError>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let &pos = self.positions.get(key).ok_or(CollectionError::InvalidHandle {
+            operation: "decrease_key",
+        })?;
+        if priority > self.heap[pos].1 {
+            return Err(CollectionError::KeyIncrease { operation: "decrease_key" });
+        }
+        self.heap[pos].1 = priority;
+        self.sift_up(pos);
+        Ok(())
+    }
+
+    /// Removes a queued key and returns its priority.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let pos = *self.positions.get(key)?;
+        Some(self.remove_at(pos).1)
+    }
+
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.positions.clear();
+    }
+
+    /// Iterates in arbitrary (heap) order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &P)> {
+        self.heap.iter().map(|(key, priority)| (key, priority))
+    }
+
+    fn remove_at(&mut self, pos: usize) -> (K, P) {
+        let last = self.heap.len() - 1;
+        self.swap(pos, last);
+        let (key, priority) = self.heap.pop().expect("The heap holds the removed entry.");
+        self.positions.remove(&key);
+        if pos < last {
+            // the entry moved into `pos` may belong above or below it
+            self.sift_up(pos);
+            self.sift_down(pos);
+        }
+        (key, priority)
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        if a == b {
+            return;
+        }
+        self.heap.swap(a, b);
+        *self.positions.get_mut(&self.heap[a].0).unwrap() = a;
+        *self.positions.get_mut(&self.heap[b].0).unwrap() = b;
+    }
+
+    fn sift_up(&mut self, mut pos: usize) {
+        while pos > 0 {
+            let parent = (pos - 1) / 2;
+            if self.heap[pos].1 >= self.heap[parent].1 {
+                break;
+            }
+            self.swap(pos, parent);
+            pos = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut pos: usize) {
+        let len = self.heap.len();
+        loop {
+            let left = 2 * pos + 1;
+            if left >= len {
+                break;
+            }
+            let right = left + 1;
+            let child = if right < len && self.heap[right].1 < self.heap[left].1 {
+                right
+            } else {
+                left
+            };
+            if self.heap[child].1 >= self.heap[pos].1 {
+                break;
+            }
+            self.swap(pos, child);
+            pos = child;
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> Default for IndexedHeap<K, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> FromIterator<(K, P)> for IndexedHeap<K, P> {
+    fn from_iter<I: IntoIterator<Item = (K, P)>>(iter: I) -> Self {
+        let mut heap = Self::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> Extend<(K, P)> for IndexedHeap<K, P> {
+    fn extend<I: IntoIterator<Item = (K, P)>>(&mut self, iter: I) {
+        for (key, priority) in iter {
+            self.push(key, priority);
+        }
+    }
+}
+
+impl<K: Debug, P: Debug> Debug for IndexedHeap<K, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.heap.iter().map(|(key, priority)| (key, priority)))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::IndexedHeap;
+    use crate::project_errors::CollectionError;
+
+    // Checks the heap property and that every key's recorded position is right.
+    fn check_heap(heap: &IndexedHeap<String, i32>) {
+        assert_eq!(heap.positions.len(), heap.heap.len());
+        for (pos, (key, priority)) in heap.heap.iter().enumerate() {
+            assert_eq!(heap.positions[key], pos);
+            if pos > 0 {
+                assert!(heap.heap[(pos - 1) / 2].1 <= *priority);
+            }
+        }
+    }
+
+    fn heap_of(entries: &[(&str, i32)]) -> IndexedHeap<String, i32> {
+        entries.iter().map(|&(key, priority)| (key.to_string(), priority)).collect()
+    }
+
+    #[test]
+    fn pops_in_priority_order() {
+        let mut heap = heap_of(&[("e", 5), ("b", 2), ("d", 4), ("a", 1), ("c", 3)]);
+        check_heap(&heap);
+        assert_eq!(heap.len(), 5);
+        assert_eq!(heap.peek(), Some((&"a".to_string(), &1)));
+        let keys: Vec<String> = std::iter::from_fn(|| heap.pop().map(|(key, _)| key)).collect();
+        assert_eq!(keys, vec!["a", "b", "c", "d", "e"]);
+        assert!(heap.is_empty() && heap.pop().is_none());
+    }
+
+    #[test]
+    fn priorities_can_change() {
+        let mut heap = heap_of(&[("a", 10), ("b", 20), ("c", 30), ("d", 40)]);
+        assert_eq!(heap.decrease_key("d", 5), Ok(()));
+        assert_eq!(heap.decrease_key("a", 10), Ok(()));
+        assert_eq!(
+            heap.decrease_key("c", 35),
+            Err(CollectionError::KeyIncrease { operation: "decrease_key" })
+        );
+        assert_eq!(
+            heap.decrease_key("x", 0),
+            Err(CollectionError::InvalidHandle { operation: "decrease_key" })
+        );
+        check_heap(&heap);
+        assert_eq!(heap.peek(), Some((&"d".to_string(), &5)));
+
+        assert_eq!(heap.change_priority("d", 50), Some(5));
+        assert_eq!(heap.change_priority("x", 50), None);
+        assert_eq!(heap.push("b".to_string(), 1), Some(20));
+        check_heap(&heap);
+        assert_eq!(heap.priority("b"), Some(&1));
+        assert_eq!(heap.len(), 4);
+
+        let order: Vec<(String, i32)> = std::iter::from_fn(|| heap.pop()).collect();
+        let order: Vec<(&str, i32)> = order.iter().map(|(key, p)| (key.as_str(), *p)).collect();
+        assert_eq!(order, vec![("b", 1), ("a", 10), ("c", 30), ("d", 50)]);
+    }
+
+    #[test]
+    fn remove_by_key() {
+        let mut heap = heap_of(&[("a", 1), ("b", 8), ("c", 2), ("d", 9), ("e", 10), ("f", 3), ("g", 4)]);
+        assert_eq!(heap.remove("d"), Some(9));
+        check_heap(&heap);
+        assert_eq!(heap.remove("d"), None);
+        assert!(!heap.contains("d") && heap.contains("g"));
+        assert_eq!(heap.remove("a"), Some(1));
+        assert_eq!(heap.remove("g"), Some(4));
+        check_heap(&heap);
+        assert_eq!(heap.pop(), Some(("c".to_string(), 2)));
+        heap.clear();
+        assert!(heap.is_empty() && !heap.contains("b"));
+    }
+}
@@ -1,4 +1,15 @@
 pub mod my_linked_list_1;
 pub mod project_errors;
 pub mod my_linked_list_2;
-pub mod mybst;
\ No newline at end of file
+pub mod mybst;
+pub mod lru;
+pub mod myrbtree;
+pub mod list_traits;
+pub mod myheap;
+pub mod indexed_heap;
+pub mod mergeable_heap;
+pub mod mygraph;
+pub mod graph_algorithms;
+
+#[cfg(test)]
+mod list_soundness;
\ No newline at end of file
@@ -0,0 +1,425 @@
+// Tests for the unsafe parts of both linked lists, kept small enough to run
+// under Miri with either aliasing model:
+//
+//     cargo +nightly miri test list_soundness
+//     MIRIFLAGS=-Zmiri-tree-borrows cargo +nightly miri test list_soundness
+//
+// Elements are heap allocated (`String`, `Box`) where possible so that double
+// frees and use-after-free show up, and Miri's leak check covers the sentinels.
+
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::my_linked_list_1::{self, MyLinkedList};
+use crate::my_linked_list_2::{self, MyLinkedList2};
+
+fn assert_send<T: Send>() {}
+fn assert_sync<T: Sync>() {}
+
+// Only has to compile: every list and read-only iterator is covariant in `T`.
+#[allow(dead_code)]
+mod covariance {
+    use crate::my_linked_list_1::{self, MyLinkedList};
+    use crate::my_linked_list_2::{self, MyLinkedList2};
+
+    fn list<'a>(x: MyLinkedList<&'static str>) -> MyLinkedList<&'a str> {
+        x
+    }
+
+    fn iter<'i, 'a>(x: my_linked_list_1::Iter<'i, &'static str>) -> my_linked_list_1::Iter<'i, &'a str> {
+        x
+    }
+
+    fn into_iter<'a>(x: my_linked_list_1::IntoIter<&'static str>) -> my_linked_list_1::IntoIter<&'a str> {
+        x
+    }
+
+    fn list2<'a>(x: MyLinkedList2<&'static str>) -> MyLinkedList2<&'a str> {
+        x
+    }
+
+    fn iter2<'i, 'a>(x: my_linked_list_2::Iter<'i, &'static str>) -> my_linked_list_2::Iter<'i, &'a str> {
+        x
+    }
+
+    fn into_iter2<'a>(x: my_linked_list_2::IntoIter<&'static str>) -> my_linked_list_2::IntoIter<&'a str> {
+        x
+    }
+}
+
+// Counts its drops and optionally panics in `drop`.
+struct DropCounter<'a> {
+    drops: &'a Cell<usize>,
+    panics: bool,
+}
+
+impl Drop for DropCounter<'_> {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+        if self.panics {
+            panic!("element drop panicked");
+        }
+    }
+}
+
+fn counters(drops: &Cell<usize>, len: usize, panic_at: usize) -> Vec<DropCounter<'_>> {
+    (0..len).map(|i| DropCounter { drops, panics: i == panic_at }).collect()
+}
+
+#[test]
+fn thread_safety_follows_the_element_type() {
+    assert_send::<MyLinkedList<String>>();
+    assert_sync::<MyLinkedList<String>>();
+    assert_send::<my_linked_list_1::Iter<'_, String>>();
+    assert_sync::<my_linked_list_1::Iter<'_, String>>();
+    assert_send::<my_linked_list_1::IterMut<'_, String>>();
+    assert_sync::<my_linked_list_1::IterMut<'_, String>>();
+    assert_send::<my_linked_list_1::IntoIter<String>>();
+    assert_send::<my_linked_list_1::Cursor<'_, String>>();
+    assert_send::<my_linked_list_1::CursorMut<'_, String>>();
+    assert_sync::<my_linked_list_1::CursorMut<'_, String>>();
+
+    assert_send::<MyLinkedList2<String>>();
+    assert_sync::<MyLinkedList2<String>>();
+    assert_send::<my_linked_list_2::Iter<'_, String>>();
+    assert_sync::<my_linked_list_2::Iter<'_, String>>();
+    assert_send::<my_linked_list_2::IterMut<'_, String>>();
+    assert_sync::<my_linked_list_2::IterMut<'_, String>>();
+    assert_send::<my_linked_list_2::IntoIter<String>>();
+
+    let list: MyLinkedList<String> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+    let list2: MyLinkedList2<String> = ["c", "d"].iter().map(|s| s.to_string()).collect();
+    let joined = std::thread::spawn(move || {
+        list.iter().chain(list2.iter()).map(String::as_str).collect::<String>()
+    });
+    assert_eq!(joined.join().unwrap(), "abcd");
+}
+
+#[test]
+fn panicking_element_drop_still_drops_the_rest() {
+    for panic_at in 0..4 {
+        let drops = Cell::new(0);
+        let list: MyLinkedList<_> = counters(&drops, 4, panic_at).into_iter().collect();
+        assert!(panic::catch_unwind(AssertUnwindSafe(|| drop(list))).is_err());
+        assert_eq!(drops.get(), 4);
+
+        let drops = Cell::new(0);
+        let list: MyLinkedList2<_> = counters(&drops, 4, panic_at).into_iter().collect();
+        assert!(panic::catch_unwind(AssertUnwindSafe(|| drop(list))).is_err());
+        assert_eq!(drops.get(), 4);
+    }
+}
+
+#[test]
+fn partly_consumed_owning_iterators_drop_the_rest() {
+    let drops = Cell::new(0);
+    let list: MyLinkedList<_> = counters(&drops, 5, 3).into_iter().collect();
+    let mut iter = list.into_iter();
+    drop(iter.next());
+    drop(iter.next_back());
+    assert_eq!(drops.get(), 2);
+    assert!(panic::catch_unwind(AssertUnwindSafe(|| drop(iter))).is_err());
+    assert_eq!(drops.get(), 5);
+
+    let drops = Cell::new(0);
+    let list: MyLinkedList2<_> = counters(&drops, 5, 3).into_iter().collect();
+    let mut iter = list.into_iter();
+    drop(iter.next());
+    drop(iter.next_back());
+    assert!(panic::catch_unwind(AssertUnwindSafe(|| drop(iter))).is_err());
+    assert_eq!(drops.get(), 5);
+}
+
+#[test]
+fn panicking_predicates_leave_lists_intact() {
+    let mut list: MyLinkedList<String> = (0..5).map(|i| i.to_string()).collect();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        list.retain(|s| if s == "3" { panic!("predicate panicked") } else { s != "1" })
+    }));
+    assert!(result.is_err());
+    assert_eq!(list.iter().map(String::as_str).collect::<String>(), "0234");
+    assert_eq!(list.iter().rev().count(), list.size());
+
+    let mut list: MyLinkedList2<String> = (0..5).map(|i| i.to_string()).collect();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        list.retain(|s| if s == "3" { panic!("predicate panicked") } else { s != "1" })
+    }));
+    assert!(result.is_err());
+    assert_eq!(list.iter().map(String::as_str).collect::<String>(), "0234");
+    assert_eq!(list.iter().rev().count(), list.get_size());
+}
+
+// Every way of alternating `next` and `next_back` must visit each element once.
+#[test]
+fn iterators_meet_in_the_middle() {
+    for len in 0..=5 {
+        let expected: Vec<i32> = (0..len as i32).collect();
+        for pattern in 0u32..(1 << len) {
+            let from_back = |step: usize| pattern & (1 << step) != 0;
+
+            let mut list = MyLinkedList::from(expected.clone());
+            let mut list2: MyLinkedList2<i32> = expected.iter().copied().collect();
+            assert_eq!(walk(list.iter().copied(), len, from_back), expected);
+            assert_eq!(walk(list2.iter().copied(), len, from_back), expected);
+
+            let bump = |x: &mut i32| {
+                *x += 10;
+                *x - 10
+            };
+            assert_eq!(walk(list.iter_mut().map(bump), len, from_back), expected);
+            assert_eq!(walk(list2.iter_mut().map(bump), len, from_back), expected);
+            assert!(list.iter().zip(&expected).all(|(x, e)| *x == e + 10));
+            assert!(list2.iter().zip(&expected).all(|(x, e)| *x == e + 10));
+
+            let plain: Vec<i32> = expected.iter().map(|x| x + 10).collect();
+            assert_eq!(walk(list.into_iter(), len, from_back), plain);
+            assert_eq!(walk(list2.into_iter(), len, from_back), plain);
+        }
+    }
+}
+
+// Drains `iter` taking from the back at the steps picked by `from_back`, and
+// returns the elements in list order.
+fn walk<I, F>(mut iter: I, len: usize, from_back: F) -> Vec<i32>
+where
+    I: DoubleEndedIterator<Item = i32> + ExactSizeIterator,
+    F: Fn(usize) -> bool,
+{
+    let mut front = Vec::new();
+    let mut back = Vec::new();
+    for step in 0..len {
+        assert_eq!(iter.len(), len - step);
+        if from_back(step) {
+            back.push(iter.next_back().expect("iterator ended early"));
+        } else {
+            front.push(iter.next().expect("iterator ended early"));
+        }
+    }
+    assert_eq!((iter.next(), iter.next_back()), (None, None));
+    front.extend(back.into_iter().rev());
+    front
+}
+
+#[test]
+fn handles_and_cursors_interleave_with_iterators() {
+    let mut list: MyLinkedList<Box<i32>> = MyLinkedList::new();
+    let handles: Vec<_> = (0..6).map(|i| list.push_back_handle(Box::new(i))).collect();
+
+    for x in list.iter_mut() {
+        **x *= 2;
+    }
+    **list.get_by_handle_mut(handles[2]).unwrap() += 1;
+    assert!(list.move_to_front(handles[5]).is_ok());
+
+    let mut cursor = list.cursor_front_mut();
+    cursor.move_next();
+    assert_eq!(cursor.remove_current().as_deref(), Some(&0));
+    cursor.insert_before(Box::new(-1));
+    let tail = cursor.split_after();
+    assert_eq!(tail.iter().map(|x| **x).collect::<Vec<_>>(), vec![5, 6, 8]);
+    assert_eq!(tail.get_by_handle(handles[3]), None);
+
+    assert_eq!(list.iter().rev().map(|x| **x).collect::<Vec<_>>(), vec![2, -1, 10]);
+    assert_eq!(list.get_by_handle(handles[5]).map(|x| **x), Some(10));
+    assert_eq!(list.get_by_handle(handles[0]), None);
+    assert_eq!(list.remove_by_handle(handles[5]).as_deref(), Some(&10));
+    assert_eq!(list.size(), 2);
+}
+
+#[test]
+fn relinking_operations_keep_links_consistent() {
+    fn strings(range: std::ops::Range<i32>) -> MyLinkedList<String> {
+        range.map(|i| i.to_string()).collect()
+    }
+    fn check(list: &MyLinkedList<String>) -> Vec<String> {
+        let forward: Vec<String> = list.iter().cloned().collect();
+        let mut backward: Vec<String> = list.iter().rev().cloned().collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+        assert_eq!(forward.len(), list.size());
+        forward
+    }
+
+    let mut list = strings(0..8);
+    list.reverse();
+    list.rotate_left(3);
+    list.swap(0, 7).unwrap();
+    list.swap(3, 4).unwrap();
+    list.sort_by_key(|s| s.parse::<i32>().unwrap() % 3);
+    check(&list);
+
+    let mut other = strings(10..14);
+    list.splice_at(4, &mut other).unwrap();
+    list.append(&mut strings(20..22));
+    list.prepend(&mut strings(30..31));
+    let mut tail = list.split_off(9);
+    tail.sort();
+    list.sort();
+    list.merge_sorted(&mut tail);
+    assert!(list.is_sorted());
+    list.dedup_by_key(|s| s.len());
+    let extracted: Vec<String> = list.extract_if(|s| s.starts_with('1')).collect();
+    assert_eq!(extracted, vec!["10"]);
+    assert_eq!(check(&list), vec!["0", "2", "20", "3", "30", "4"]);
+
+    let mut list2: MyLinkedList2<String> = (0..8).map(|i| i.to_string()).collect();
+    list2.sort_by(|a, b| b.cmp(a));
+    list2.sort();
+    list2.merge_sorted(&mut (0..3).map(|i| (i * 3).to_string()).collect());
+    list2.dedup();
+    list2.retain_mut(|s| {
+        s.push('!');
+        s != "7!"
+    });
+    let extracted: Vec<String> = list2.extract_if(|s| s.starts_with('6')).collect();
+    assert_eq!(extracted, vec!["6!"]);
+    let forward: Vec<&str> = list2.iter().map(String::as_str).collect();
+    assert_eq!(forward, vec!["0!", "1!", "2!", "3!", "4!", "5!"]);
+    assert_eq!(list2.iter().rev().count(), list2.get_size());
+}
+
+thread_local! {
+    static COMPARISONS_LEFT: Cell<usize> = const { Cell::new(usize::MAX) };
+}
+
+// Orders like the boxed `i32`, but panics once `COMPARISONS_LEFT` runs out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Touchy(Box<i32>);
+
+impl PartialOrd for Touchy {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Touchy {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        if COMPARISONS_LEFT.with(|left| left.replace(left.get().saturating_sub(1))) == 0 {
+            panic!("comparison panicked");
+        }
+        self.0.cmp(&other.0)
+    }
+}
+
+// Runs `f` with a budget of `comparisons`, and reports whether it panicked.
+fn with_comparisons<R>(comparisons: usize, f: impl FnOnce() -> R) -> bool {
+    COMPARISONS_LEFT.with(|left| left.set(comparisons));
+    let panicked = panic::catch_unwind(AssertUnwindSafe(f)).is_err();
+    COMPARISONS_LEFT.with(|left| left.set(usize::MAX));
+    panicked
+}
+
+fn touchy(values: &[i32]) -> Vec<Touchy> {
+    values.iter().map(|&v| Touchy(Box::new(v))).collect()
+}
+
+// Checks that walking forwards and backwards agree with each other and with
+// `len`, and returns the elements in list order.
+fn linked<'a, I, F>(iter: F, len: usize) -> Vec<i32>
+where
+    I: DoubleEndedIterator<Item = &'a Touchy>,
+    F: Fn() -> I,
+{
+    let forward: Vec<i32> = iter().map(|t| *t.0).collect();
+    let mut backward: Vec<i32> = iter().rev().map(|t| *t.0).collect();
+    backward.reverse();
+    assert_eq!(forward, backward);
+    assert_eq!(forward.len(), len);
+    forward
+}
+
+fn sorted(mut values: Vec<i32>) -> Vec<i32> {
+    values.sort();
+    values
+}
+
+#[test]
+fn panicking_comparisons_keep_every_element() {
+    let values = [5, 3, 8, 1, 9, 2, 7, 3];
+    let mut panic_at = 0;
+    loop {
+        let mut list = MyLinkedList::from(touchy(&values));
+        let mut list2: MyLinkedList2<Touchy> = touchy(&values).into_iter().collect();
+        let panicked = with_comparisons(panic_at, || list.sort());
+        assert_eq!(with_comparisons(panic_at, || list2.sort()), panicked);
+        assert_eq!(sorted(linked(|| list.iter(), list.size())), sorted(values.to_vec()));
+        assert_eq!(sorted(linked(|| list2.iter(), list2.get_size())), sorted(values.to_vec()));
+
+        let mut list = MyLinkedList::from(touchy(&values));
+        let mut list2: MyLinkedList2<Touchy> = touchy(&values).into_iter().collect();
+        let key = |t: &Touchy| t.clone();
+        with_comparisons(panic_at, || list.sort_by_key(key));
+        with_comparisons(panic_at, || list2.sort_by_key(key));
+        assert_eq!(sorted(linked(|| list.iter(), list.size())), sorted(values.to_vec()));
+        assert_eq!(sorted(linked(|| list2.iter(), list2.get_size())), sorted(values.to_vec()));
+
+        // whatever `merge_sorted` has not taken yet stays in `other`
+        let (left, right) = ([1, 3, 3, 8], [2, 3, 5, 9, 10]);
+        let mut list = MyLinkedList::from(touchy(&left));
+        let mut other = MyLinkedList::from(touchy(&right));
+        with_comparisons(panic_at, || list.merge_sorted(&mut other));
+        let mut all = linked(|| list.iter(), list.size());
+        all.extend(linked(|| other.iter(), other.size()));
+        assert_eq!(sorted(all), sorted([&left[..], &right[..]].concat()));
+
+        let mut list2: MyLinkedList2<Touchy> = touchy(&left).into_iter().collect();
+        let mut other2: MyLinkedList2<Touchy> = touchy(&right).into_iter().collect();
+        with_comparisons(panic_at, || list2.merge_sorted(&mut other2));
+        let mut all = linked(|| list2.iter(), list2.get_size());
+        all.extend(linked(|| other2.iter(), other2.get_size()));
+        assert_eq!(sorted(all), sorted([&left[..], &right[..]].concat()));
+
+        if !panicked {
+            break;
+        }
+        panic_at += 1;
+    }
+}
+
+#[test]
+fn panicking_dedup_and_retain_keep_links_consistent() {
+    let values = [1, 1, 2, 3, 3, 3, 4, 5, 5];
+    // `dedup_by` makes one call fewer than there are elements
+    for panic_at in 0..values.len() - 1 {
+        let calls = Cell::new(0);
+        let same_bucket = |a: &mut Touchy, b: &mut Touchy| {
+            calls.set(calls.get() + 1);
+            if calls.get() > panic_at {
+                panic!("same_bucket panicked");
+            }
+            a == b
+        };
+
+        let mut list = MyLinkedList::from(touchy(&values));
+        assert!(panic::catch_unwind(AssertUnwindSafe(|| list.dedup_by(same_bucket))).is_err());
+        let kept = linked(|| list.iter(), list.size());
+        list.dedup();
+        assert_eq!(linked(|| list.iter(), list.size()), vec![1, 2, 3, 4, 5]);
+        assert!(kept.len() >= 5);
+
+        calls.set(0);
+        let mut list2: MyLinkedList2<Touchy> = touchy(&values).into_iter().collect();
+        assert!(panic::catch_unwind(AssertUnwindSafe(|| list2.dedup_by(same_bucket))).is_err());
+        assert_eq!(linked(|| list2.iter(), list2.get_size()), kept);
+        list2.dedup();
+        assert_eq!(linked(|| list2.iter(), list2.get_size()), vec![1, 2, 3, 4, 5]);
+
+        let calls = Cell::new(0);
+        let keep = |t: &Touchy| {
+            calls.set(calls.get() + 1);
+            if calls.get() > panic_at {
+                panic!("retain panicked");
+            }
+            *t.0 % 2 == 1
+        };
+        let mut list = MyLinkedList::from(touchy(&values));
+        assert!(panic::catch_unwind(AssertUnwindSafe(|| list.retain(keep))).is_err());
+        let kept = linked(|| list.iter(), list.size());
+        assert_eq!(kept.len(), values.len() - values[..panic_at].iter().filter(|&&v| v % 2 == 0).count());
+
+        calls.set(0);
+        let mut list2: MyLinkedList2<Touchy> = touchy(&values).into_iter().collect();
+        assert!(panic::catch_unwind(AssertUnwindSafe(|| list2.retain(keep))).is_err());
+        assert_eq!(linked(|| list2.iter(), list2.get_size()), kept);
+    }
+}
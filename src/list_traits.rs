@@ -0,0 +1,318 @@
+// Common interfaces over the crate's lists, so code can be written once and
+// benchmarked against `MyLinkedList`, `MyLinkedList2` and `VecDeque`.
+
+use std::collections::{vec_deque, VecDeque};
+use std::mem;
+
+use crate::my_linked_list_1::{self, MyLinkedList};
+use crate::my_linked_list_2::{self, MyLinkedList2};
+use crate::project_errors::CollectionError;
+
+/// A double-ended queue.
+pub trait Deque<T> {
+    type Iter<'a>: DoubleEndedIterator<Item = &'a T>
+    where
+        Self: 'a,
+        T: 'a;
+
+    fn push_front(&mut self, value: T);
+    fn push_back(&mut self, value: T);
+    fn pop_front(&mut self) -> Option<T>;
+    fn pop_back(&mut self) -> Option<T>;
+    fn peek_front(&self) -> Option<&T>;
+    fn peek_back(&self) -> Option<&T>;
+    fn len(&self) -> usize;
+    fn clear(&mut self);
+    fn iter(&self) -> Self::Iter<'_>;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Positional access to a list.
+pub trait IndexedList<T> {
+    fn get(&self, index: usize) -> Option<&T>;
+    fn get_mut(&mut self, index: usize) -> Option<&mut T>;
+    /// Replaces the element at `index` and returns the old one.
+    fn set(&mut self, index: usize, value: T) -> Result<T, CollectionError>;
+    /// Inserts `value` so that it ends up at `index`; `index == len` appends.
+    fn insert(&mut self, index: usize, value: T) -> Result<(), CollectionError>;
+    fn remove(&mut self, index: usize) -> Result<T, CollectionError>;
+}
+
+fn out_of_range(operation: &'static str, index: usize, len: usize) -> CollectionError {
+    CollectionError::IndexOutOfRange { operation, index, len }
+}
+
+impl<T> Deque<T> for MyLinkedList<T> {
+    type Iter<'a> = my_linked_list_1::Iter<'a, T> where T: 'a;
+
+    fn push_front(&mut self, value: T) {
+        MyLinkedList::push_front(self, value)
+    }
+
+    fn push_back(&mut self, value: T) {
+        MyLinkedList::push_back(self, value)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        MyLinkedList::pop_front(self)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        MyLinkedList::pop_back(self)
+    }
+
+    fn peek_front(&self) -> Option<&T> {
+        MyLinkedList::peek_front(self)
+    }
+
+    fn peek_back(&self) -> Option<&T> {
+        MyLinkedList::peek_back(self)
+    }
+
+    fn len(&self) -> usize {
+        self.size()
+    }
+
+    fn clear(&mut self) {
+        MyLinkedList::clear(self)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        MyLinkedList::iter(self)
+    }
+}
+
+impl<T> IndexedList<T> for MyLinkedList<T> {
+    fn get(&self, index: usize) -> Option<&T> {
+        MyLinkedList::get(self, index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        MyLinkedList::get_mut(self, index)
+    }
+
+    fn set(&mut self, index: usize, value: T) -> Result<T, CollectionError> {
+        let len = self.size();
+        let slot = MyLinkedList::get_mut(self, index).ok_or(out_of_range("set", index, len))?;
+        Ok(mem::replace(slot, value))
+    }
+
+    fn insert(&mut self, index: usize, value: T) -> Result<(), CollectionError> {
+        MyLinkedList::insert(self, index, value)
+    }
+
+    fn remove(&mut self, index: usize) -> Result<T, CollectionError> {
+        MyLinkedList::remove(self, index)
+    }
+}
+
+impl<T> Deque<T> for MyLinkedList2<T> {
+    type Iter<'a> = my_linked_list_2::Iter<'a, T> where T: 'a;
+
+    fn push_front(&mut self, value: T) {
+        MyLinkedList2::push_front(self, value)
+    }
+
+    fn push_back(&mut self, value: T) {
+        MyLinkedList2::push_back(self, value)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        MyLinkedList2::pop_front(self).ok()
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        MyLinkedList2::pop_back(self).ok()
+    }
+
+    fn peek_front(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    fn peek_back(&self) -> Option<&T> {
+        MyLinkedList2::peek_back(self)
+    }
+
+    fn len(&self) -> usize {
+        self.get_size()
+    }
+
+    fn clear(&mut self) {
+        while MyLinkedList2::pop_front(self).is_ok() {}
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        MyLinkedList2::iter(self)
+    }
+}
+
+impl<T> IndexedList<T> for MyLinkedList2<T> {
+    fn get(&self, index: usize) -> Option<&T> {
+        MyLinkedList2::get(self, index).ok().flatten()
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        MyLinkedList2::get_mut(self, index).ok().flatten()
+    }
+
+    fn set(&mut self, index: usize, value: T) -> Result<T, CollectionError> {
+        let len = self.get_size();
+        let slot = IndexedList::get_mut(self, index).ok_or(out_of_range("set", index, len))?;
+        Ok(mem::replace(slot, value))
+    }
+
+    fn insert(&mut self, index: usize, value: T) -> Result<(), CollectionError> {
+        MyLinkedList2::insert(self, index, value)
+    }
+
+    fn remove(&mut self, index: usize) -> Result<T, CollectionError> {
+        let len = self.get_size();
+        MyLinkedList2::remove(self, index)
+            .map_err(|_| out_of_range("remove", index, len))?
+            .ok_or(out_of_range("remove", index, len))
+    }
+}
+
+// The standard library deque, as a reference implementation.
+impl<T> Deque<T> for VecDeque<T> {
+    type Iter<'a> = vec_deque::Iter<'a, T> where T: 'a;
+
+    fn push_front(&mut self, value: T) {
+        VecDeque::push_front(self, value)
+    }
+
+    fn push_back(&mut self, value: T) {
+        VecDeque::push_back(self, value)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        VecDeque::pop_front(self)
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        VecDeque::pop_back(self)
+    }
+
+    fn peek_front(&self) -> Option<&T> {
+        self.front()
+    }
+
+    fn peek_back(&self) -> Option<&T> {
+        self.back()
+    }
+
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+
+    fn clear(&mut self) {
+        VecDeque::clear(self)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        VecDeque::iter(self)
+    }
+}
+
+impl<T> IndexedList<T> for VecDeque<T> {
+    fn get(&self, index: usize) -> Option<&T> {
+        VecDeque::get(self, index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        VecDeque::get_mut(self, index)
+    }
+
+    fn set(&mut self, index: usize, value: T) -> Result<T, CollectionError> {
+        let len = VecDeque::len(self);
+        let slot = VecDeque::get_mut(self, index).ok_or(out_of_range("set", index, len))?;
+        Ok(mem::replace(slot, value))
+    }
+
+    fn insert(&mut self, index: usize, value: T) -> Result<(), CollectionError> {
+        let len = VecDeque::len(self);
+        if index > len {
+            return Err(out_of_range("insert", index, len));
+        }
+        VecDeque::insert(self, index, value);
+        Ok(())
+    }
+
+    fn remove(&mut self, index: usize) -> Result<T, CollectionError> {
+        let len = VecDeque::len(self);
+        VecDeque::remove(self, index).ok_or(out_of_range("remove", index, len))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Deque, IndexedList};
+    use crate::my_linked_list_1::MyLinkedList;
+    use crate::my_linked_list_2::MyLinkedList2;
+    use crate::project_errors::CollectionError;
+    use std::collections::VecDeque;
+
+    fn exercise_deque<D: Deque<i32> + Default>() {
+        let mut deque = D::default();
+        assert!(deque.is_empty());
+        assert_eq!(deque.pop_front(), None);
+        deque.push_back(2);
+        deque.push_front(1);
+        deque.push_back(3);
+        assert_eq!((deque.peek_front(), deque.peek_back()), (Some(&1), Some(&3)));
+        assert_eq!(deque.iter().rev().copied().collect::<Vec<_>>(), vec![3, 2, 1]);
+        assert_eq!(deque.pop_back(), Some(3));
+        assert_eq!(deque.pop_front(), Some(1));
+        assert_eq!(deque.len(), 1);
+        deque.clear();
+        assert!(deque.is_empty());
+        assert_eq!(deque.peek_back(), None);
+    }
+
+    fn exercise_indexed<L: Deque<i32> + IndexedList<i32> + Default>() {
+        let mut list = L::default();
+        for x in [10, 20, 30, 40, 50] {
+            list.push_back(x);
+        }
+        let values: Vec<_> = (0..5).map(|i| list.get(i).copied()).collect();
+        assert_eq!(values, vec![Some(10), Some(20), Some(30), Some(40), Some(50)]);
+        assert_eq!(list.get(5), None);
+
+        *list.get_mut(3).unwrap() += 1;
+        assert_eq!(list.set(1, 21), Ok(20));
+        assert!(list.insert(0, 0).is_ok());
+        assert!(list.insert(6, 60).is_ok());
+        assert!(list.insert(4, 35).is_ok());
+        assert_eq!(
+            list.insert(9, 0),
+            Err(CollectionError::IndexOutOfRange { operation: "insert", index: 9, len: 8 })
+        );
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 10, 21, 30, 35, 41, 50, 60]);
+
+        assert_eq!(list.remove(5), Ok(41));
+        assert_eq!(list.remove(0), Ok(0));
+        assert_eq!(list.remove(5), Ok(60));
+        assert_eq!(
+            list.remove(5),
+            Err(CollectionError::IndexOutOfRange { operation: "remove", index: 5, len: 5 })
+        );
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![10, 21, 30, 35, 50]);
+    }
+
+    #[test]
+    fn all_lists_behave_like_a_deque() {
+        exercise_deque::<MyLinkedList<i32>>();
+        exercise_deque::<MyLinkedList2<i32>>();
+        exercise_deque::<VecDeque<i32>>();
+    }
+
+    #[test]
+    fn all_lists_support_indexed_access() {
+        exercise_indexed::<MyLinkedList<i32>>();
+        exercise_indexed::<MyLinkedList2<i32>>();
+        exercise_indexed::<VecDeque<i32>>();
+    }
+}
@@ -0,0 +1,211 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::my_linked_list_1::{MyLinkedList, NodeHandle};
+
+type EvictCallback<K, V> = Box<dyn FnMut(K, V)>;
+
+/// A least-recently-used cache.
+///
+/// Entries live in a `MyLinkedList` ordered from most to least recently used,
+/// and a `HashMap` maps every key to the handle of its node, so lookups,
+/// promotions and evictions are all O(1).
+pub struct LruCache<K, V> {
+    capacity: usize,
+    map: HashMap<K, NodeHandle>,
+    list: MyLinkedList<(K, V)>,
+    on_evict: Option<EvictCallback<K, V>>,
+}
+
+impl<K, V> LruCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            map: HashMap::with_capacity(capacity),
+            list: MyLinkedList::new(),
+            on_evict: None,
+        }
+    }
+
+    /// Creates a cache that hands every entry evicted for lack of room to `on_evict`.
+    /// Entries removed explicitly through `pop_lru` or `remove` are not reported.
+    pub fn with_evict_callback<F>(capacity: usize, on_evict: F) -> Self
+    where
+        F: FnMut(K, V) + 'static,
+    {
+        let mut cache = Self::new(capacity);
+        cache.on_evict = Some(Box::new(on_evict));
+        cache
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    /// Returns the value for `key` and marks it as most recently used.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let handle = *self.map.get(key)?;
+        self.list.move_to_front(handle).ok()?;
+        self.list.get_by_handle(handle).map(|(_, value)| value)
+    }
+
+    /// Mutable version of `get`; also marks the entry as most recently used.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let handle = *self.map.get(key)?;
+        self.list.move_to_front(handle).ok()?;
+        self.list.get_by_handle_mut(handle).map(|(_, value)| value)
+    }
+
+    /// Returns the value for `key` without touching the recency order.
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let handle = *self.map.get(key)?;
+        self.list.get_by_handle(handle).map(|(_, value)| value)
+    }
+
+    /// The least recently used entry, without touching the recency order.
+    pub fn peek_lru(&self) -> Option<(&K, &V)> {
+        self.list.peek_back().map(|(key, value)| (key, value))
+    }
+
+    /// Inserts `value` as the most recently used entry and returns the value
+    /// previously stored under `key`, evicting the least recently used entry
+    /// if the cache is full.
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(&handle) = self.map.get(&key) {
+            self.list.move_to_front(handle).ok()?;
+            let (_, old) = self.list.get_by_handle_mut(handle)?;
+            return Some(std::mem::replace(old, value));
+        }
+
+        let handle = self.list.push_front_handle((key.clone(), value));
+        self.map.insert(key, handle);
+        self.evict_to(self.capacity);
+        None
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let handle = self.map.remove(key)?;
+        self.list.remove_by_handle(handle).map(|(_, value)| value)
+    }
+
+    /// Removes and returns the least recently used entry.
+    pub fn pop_lru(&mut self) -> Option<(K, V)> {
+        let (key, value) = self.list.pop_back()?;
+        self.map.remove(&key);
+        Some((key, value))
+    }
+
+    /// Changes the capacity, evicting least recently used entries if the cache shrinks.
+    pub fn resize(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict_to(capacity);
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.list.clear();
+    }
+
+    /// Iterates from the most to the least recently used entry.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&K, &V)> {
+        self.list.iter().map(|(key, value)| (key, value))
+    }
+
+    fn evict_to(&mut self, capacity: usize) {
+        while self.map.len() > capacity {
+            let Some((key, value)) = self.pop_lru() else {
+                break;
+            };
+            if let Some(on_evict) = self.on_evict.as_mut() {
+                on_evict(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LruCache;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        assert_eq!(cache.put("a", 1), None);
+        assert_eq!(cache.put("b", 2), None);
+        assert_eq!(cache.get(&"a"), Some(&1));
+        cache.put("c", 3);
+
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.contains(&"b"));
+        assert_eq!(cache.peek_lru(), Some((&"a", &1)));
+        assert_eq!(cache.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec!["c", "a"]);
+    }
+
+    #[test]
+    fn put_existing_key_replaces_and_promotes() {
+        let mut cache = LruCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        assert_eq!(cache.put("a", 10), Some(1));
+        *cache.get_mut(&"b").unwrap() += 1;
+        // peek must not promote "a" over "b"
+        assert_eq!(cache.peek(&"a"), Some(&10));
+        assert_eq!(cache.pop_lru(), Some(("a", 10)));
+        assert_eq!(cache.remove(&"b"), Some(3));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn resize_reports_evictions() {
+        let evicted = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&evicted);
+        let mut cache = LruCache::with_evict_callback(3, move |k, v| sink.borrow_mut().push((k, v)));
+        for (k, v) in [(1, 'a'), (2, 'b'), (3, 'c'), (4, 'd')] {
+            cache.put(k, v);
+        }
+        cache.resize(1);
+        cache.pop_lru();
+
+        assert_eq!(cache.capacity(), 1);
+        assert!(cache.is_empty());
+        assert_eq!(*evicted.borrow(), vec![(1, 'a'), (2, 'b'), (3, 'c')]);
+    }
+}
@@ -1,28 +1,47 @@
 use std::collections::BinaryHeap;
 use std::cmp::Reverse;
 
+use rust_dsa::indexed_heap::IndexedHeap;
+use rust_dsa::mergeable_heap::{BinomialHeap, PairingHeap};
+use rust_dsa::myheap::MyHeap;
 
 fn main() {
-    let my_heap: BinaryHeap<i32> = BinaryHeap::from(vec![11, 2, 5, 6, 7, 8, 9, 12, 4, 10, 1, 3, 15, 13, 14]);
+    let values = vec![11, 2, 5, 6, 7, 8, 9, 12, 4, 10, 1, 3, 15, 13, 14];
+    let my_heap: BinaryHeap<i32> = BinaryHeap::from(values.clone());
 
-    // let mut second_head :BinaryHeap<Reverse<i32>> = BinaryHeap::new();
-
-    // second_head.push(Reverse(10));
-    // second_head.push(Reverse(6));
-    // second_head.push(Reverse(5));
-    // second_head.push(Reverse(4));
-    // second_head.push(Reverse(9));
-    // second_head.push(Reverse(16));
-    // second_head.push(Reverse(7));
-    // second_head.push(Reverse(18));
-    // second_head.push(Reverse(22));
-    // second_head.push(Reverse(1));
-    // second_head.push(Reverse(3));
-    // second_head.push(Reverse(8));
-    // second_head.push(Reverse(2));
-    // second_head.push(Reverse(11));
+    // std's BinaryHeap is a max-heap; wrapping in `Reverse` turns it into a min-heap
+    let mut second_head: BinaryHeap<Reverse<i32>> = BinaryHeap::new();
+    for x in [10, 6, 5, 4, 9, 16, 7, 18, 22, 1, 3, 8, 2, 11] {
+        second_head.push(Reverse(x));
+    }
 
     let sort_vec = my_heap;
     println!("{:?}", sort_vec);
-    // println!("{:?}", second_head);
-}
\ No newline at end of file
+    println!("{:?}", second_head.into_sorted_vec());
+
+    // the same with our own heap, where the order comes from the comparator
+    let native_max: MyHeap<i32> = MyHeap::from(values.clone());
+    let mut native_min = MyHeap::new_min();
+    native_min.extend(values);
+    println!("{:?}", native_max);
+    println!("{:?}", native_min.drain_sorted().collect::<Vec<_>>());
+
+    // queued items can be re-prioritized by key
+    let mut tasks: IndexedHeap<&str, u32> = IndexedHeap::new();
+    tasks.push("compile", 30);
+    tasks.push("test", 20);
+    tasks.push("deploy", 40);
+    tasks.decrease_key("deploy", 10).unwrap();
+    println!("{:?}", std::iter::from_fn(|| tasks.pop()).collect::<Vec<_>>());
+
+    // pointer-based heaps meld without copying; handles into the absorbed heap go stale
+    let mut worker_a: PairingHeap<u32> = [7, 3, 9].into_iter().collect();
+    let mut worker_b = PairingHeap::new();
+    let late = worker_b.push(12);
+    worker_a.meld(&mut worker_b);
+    println!("{:?}", std::iter::from_fn(|| worker_a.pop()).collect::<Vec<_>>());
+    let mut binomial: BinomialHeap<u32> = (10..16).collect();
+    let last = binomial.push(16);
+    binomial.decrease_key(last, 1).unwrap();
+    println!("{:?} {:?}", binomial.peek(), worker_a.get(late));
+}
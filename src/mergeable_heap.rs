@@ -0,0 +1,915 @@
+// Pointer-based min-heaps that meld without copying, for merging the queues
+// of partitioned workers. Nodes are `NonNull` boxes like in `MyLinkedList`.
+
+use std::{
+    marker::PhantomData,
+    mem,
+    ptr::NonNull,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::project_errors::CollectionError;
+
+// Every heap gets its own id so handles from another heap are rejected.
+static NEXT_HEAP_ID: AtomicU64 = AtomicU64::new(0);
+
+/// An opaque reference to an element of a `PairingHeap` or `BinomialHeap`.
+///
+/// Like `NodeHandle`, it is checked on every use. It goes stale when its
+/// element is popped, or when its heap is melded into another heap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HeapHandle {
+    heap_id: u64,
+    slot: usize,
+    generation: u64,
+}
+
+struct Slot<N> {
+    generation: u64,
+    node: Option<NonNull<N>>,
+}
+
+// Maps handles to nodes. Every node remembers the id of the table that tracks
+// it, so nodes melded in from another heap stay untracked and `meld` never
+// has to visit them.
+struct SlotTable<N> {
+    id: u64,
+    slots: Vec<Slot<N>>,
+    free: Vec<usize>,
+}
+
+impl<N> SlotTable<N> {
+    fn new() -> Self {
+        Self {
+            id: NEXT_HEAP_ID.fetch_add(1, Ordering::Relaxed),
+            slots: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    // Returns the slot for the node and a handle to it.
+    fn track(&mut self, node: NonNull<N>) -> (usize, HeapHandle) {
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.slots[slot].node = Some(node);
+                slot
+            }
+            None => {
+                self.slots.push(Slot { generation: 0, node: Some(node) });
+                self.slots.len() - 1
+            }
+        };
+        let handle = HeapHandle {
+            heap_id: self.id,
+            slot,
+            generation: self.slots[slot].generation,
+        };
+        (slot, handle)
+    }
+
+    fn get(&self, handle: HeapHandle) -> Option<NonNull<N>> {
+        if handle.heap_id != self.id {
+            return None;
+        }
+        let slot = self.slots.get(handle.slot)?;
+        if slot.generation != handle.generation {
+            return None;
+        }
+        slot.node
+    }
+
+    fn release(&mut self, owner: u64, slot: usize) {
+        if owner != self.id {
+            return;
+        }
+        let entry = &mut self.slots[slot];
+        entry.generation += 1;
+        entry.node = None;
+        self.free.push(slot);
+    }
+
+    // Points a slot at the node that now holds its element.
+    fn relocate(&mut self, owner: u64, slot: usize, node: NonNull<N>) {
+        if owner == self.id {
+            self.slots[slot].node = Some(node);
+        }
+    }
+
+    // Forgets every handle, e.g. after the nodes moved to another heap.
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+// Empties a heap whose links are being rebuilt if a panicking `Ord`
+// interrupts the rebuild. The nodes are leaked, but no link or handle is left
+// pointing at a half-merged structure. `disarm` once the rebuild is done.
+struct EmptyOnUnwind<'a, N> {
+    root: &'a mut Option<NonNull<N>>,
+    len: &'a mut usize,
+    slots: &'a mut SlotTable<N>,
+}
+
+impl<'a, N> EmptyOnUnwind<'a, N> {
+    fn new(root: &'a mut Option<NonNull<N>>, len: &'a mut usize, slots: &'a mut SlotTable<N>) -> Self {
+        Self { root, len, slots }
+    }
+
+    fn disarm(self) {
+        mem::forget(self);
+    }
+}
+
+impl<N> Drop for EmptyOnUnwind<'_, N> {
+    fn drop(&mut self) {
+        *self.root = None;
+        *self.len = 0;
+        self.slots.reset();
+    }
+}
+
+struct PairingNode<T> {
+    value: T,
+    child: Option<NonNull<PairingNode<T>>>,
+    sibling: Option<NonNull<PairingNode<T>>>,
+    // The parent for a first child, the previous sibling otherwise.
+    prev: Option<NonNull<PairingNode<T>>>,
+    owner: u64,
+    slot: usize,
+}
+
+/// A pairing heap: O(1) `push`, `meld` and `decrease_key`, amortized
+/// O(log n) `pop`.
+pub struct PairingHeap<T> {
+    root: Option<NonNull<PairingNode<T>>>,
+    len: usize,
+    slots: SlotTable<PairingNode<T>>,
+    _marker: PhantomData<Box<PairingNode<T>>>,
+}
+
+// The heap owns its nodes like a `Box` would, so it is as thread-safe as `T`.
+unsafe impl<T: Send> Send for PairingHeap<T> {}
+unsafe impl<T: Sync> Sync for PairingHeap<T> {}
+
+impl<T: Ord> PairingHeap<T> {
+    pub fn new() -> Self {
+        Self {
+            root: None,
+            len: 0,
+            slots: SlotTable::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// The smallest element.
+    pub fn peek(&self) -> Option<&T> {
+        self.root.map(|root| unsafe { &(*root.as_ptr()).value })
+    }
+
+    pub fn push(&mut self, value: T) -> HeapHandle {
+        let node = NonNull::from(Box::leak(Box::new(PairingNode {
+            value,
+            child: None,
+            sibling: None,
+            prev: None,
+            owner: self.slots.id,
+            slot: 0,
+        })));
+        // `link` compares before it relinks, so if `Ord` panics the new
+        // node is only leaked
+        self.root = Self::link(self.root, Some(node));
+        let (slot, handle) = self.slots.track(node);
+        unsafe {
+            (*node.as_ptr()).slot = slot;
+        }
+        self.len += 1;
+        handle
+    }
+
+    /// Removes the smallest element. If `Ord` panics while the children of
+    /// the old root are merged, the heap is left empty and its elements leak.
+    pub fn pop(&mut self) -> Option<T> {
+        let root = self.root?;
+        let rebuild = EmptyOnUnwind::new(&mut self.root, &mut self.len, &mut self.slots);
+        let new_root = unsafe { Self::merge_pairs((*root.as_ptr()).child) };
+        rebuild.disarm();
+
+        self.root = new_root;
+        if let Some(new_root) = new_root {
+            unsafe {
+                (*new_root.as_ptr()).prev = None;
+            }
+        }
+        let node = unsafe { Box::from_raw(root.as_ptr()) };
+        self.slots.release(node.owner, node.slot);
+        self.len -= 1;
+        Some(node.value)
+    }
+
+    /// Moves every element of `other` into `self` in O(1), leaving `other`
+    /// empty. Handles into `other` become stale.
+    pub fn meld(&mut self, other: &mut Self) {
+        // nothing changes if the comparison in `link` panics
+        self.root = Self::link(self.root, other.root);
+        other.root = None;
+        self.len += mem::take(&mut other.len);
+        other.slots.reset();
+    }
+
+    pub fn get(&self, handle: HeapHandle) -> Option<&T> {
+        self.slots.get(handle).map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    pub fn contains(&self, handle: HeapHandle) -> bool {
+        self.slots.get(handle).is_some()
+    }
+
+    /// Replaces the element behind `handle` with a smaller (or equal) one.
+    pub fn decrease_key(&mut self, handle: HeapHandle, value: T) -> Result<(), CollectionError> {
+        let node = self.slots.get(handle).ok_or(CollectionError::InvalidHandle {
+            operation: "decrease_key",
+        })?;
+        unsafe {
+            if value > (*node.as_ptr()).value {
+                return Err(CollectionError::KeyIncrease { operation: "decrease_key" });
+            }
+            (*node.as_ptr()).value = value;
+            if let Some(root) = self.root.filter(|&root| root != node) {
+                // the subtree stays a valid heap, so cut it and link it back in
+                let rebuild = EmptyOnUnwind::new(&mut self.root, &mut self.len, &mut self.slots);
+                Self::cut(node);
+                let new_root = Self::link(Some(root), Some(node));
+                rebuild.disarm();
+                self.root = new_root;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    // Links two roots, making the larger one the first child of the smaller.
+    fn link(
+        a: Option<NonNull<PairingNode<T>>>,
+        b: Option<NonNull<PairingNode<T>>>,
+    ) -> Option<NonNull<PairingNode<T>>> {
+        match (a, b) {
+            (None, root) | (root, None) => root,
+            (Some(a), Some(b)) => unsafe {
+                let (parent, child) = if (*b.as_ptr()).value < (*a.as_ptr()).value {
+                    (b, a)
+                } else {
+                    (a, b)
+                };
+                (*child.as_ptr()).sibling = (*parent.as_ptr()).child;
+                if let Some(first) = (*parent.as_ptr()).child {
+                    (*first.as_ptr()).prev = Some(child);
+                }
+                (*child.as_ptr()).prev = Some(parent);
+                (*parent.as_ptr()).child = Some(child);
+                Some(parent)
+            },
+        }
+    }
+
+    // Two-pass pairing of a sibling list into a single root: link neighbours
+    // left to right, then fold the pairs right to left.
+    unsafe fn merge_pairs(first: Option<NonNull<PairingNode<T>>>) -> Option<NonNull<PairingNode<T>>> {
+        // the pairs are stacked through their `sibling` field
+        let mut pairs: Option<NonNull<PairingNode<T>>> = None;
+        let mut current = first;
+        while let Some(a) = current {
+            let b = (*a.as_ptr()).sibling;
+            current = b.and_then(|b| (*b.as_ptr()).sibling);
+            for node in [Some(a), b].into_iter().flatten() {
+                (*node.as_ptr()).sibling = None;
+                (*node.as_ptr()).prev = None;
+            }
+            let pair = Self::link(Some(a), b).expect("Linking a non-empty pair.");
+            (*pair.as_ptr()).sibling = pairs;
+            pairs = Some(pair);
+        }
+
+        let mut root = None;
+        while let Some(pair) = pairs {
+            pairs = (*pair.as_ptr()).sibling;
+            (*pair.as_ptr()).sibling = None;
+            root = Self::link(root, Some(pair));
+        }
+        root
+    }
+
+    // Detaches a non-root node, with its subtree, from its parent or siblings.
+    unsafe fn cut(node: NonNull<PairingNode<T>>) {
+        let prev = (*node.as_ptr()).prev.expect("A non-root node has a prev link.");
+        let sibling = (*node.as_ptr()).sibling;
+        if (*prev.as_ptr()).child == Some(node) {
+            (*prev.as_ptr()).child = sibling;
+        } else {
+            (*prev.as_ptr()).sibling = sibling;
+        }
+        if let Some(sibling) = sibling {
+            (*sibling.as_ptr()).prev = Some(prev);
+        }
+        (*node.as_ptr()).sibling = None;
+        (*node.as_ptr()).prev = None;
+    }
+}
+
+impl<T> Drop for PairingHeap<T> {
+    fn drop(&mut self) {
+        let mut pending: Vec<NonNull<PairingNode<T>>> = self.root.take().into_iter().collect();
+        while let Some(node) = pending.pop() {
+            let node = unsafe { Box::from_raw(node.as_ptr()) };
+            pending.extend(node.child);
+            pending.extend(node.sibling);
+        }
+    }
+}
+
+impl<T: Ord> Default for PairingHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> Extend<T> for PairingHeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T: Ord> FromIterator<T> for PairingHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = Self::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+struct BinomialNode<T> {
+    value: T,
+    parent: Option<NonNull<BinomialNode<T>>>,
+    child: Option<NonNull<BinomialNode<T>>>,
+    sibling: Option<NonNull<BinomialNode<T>>>,
+    degree: usize,
+    owner: u64,
+    slot: usize,
+}
+
+// A root and the root before it in the root list.
+type RootEntry<T> = (NonNull<BinomialNode<T>>, Option<NonNull<BinomialNode<T>>>);
+
+/// A binomial heap: O(log n) `push`, `pop`, `meld` and `decrease_key`.
+pub struct BinomialHeap<T> {
+    // Roots linked through `sibling`, by increasing degree.
+    head: Option<NonNull<BinomialNode<T>>>,
+    len: usize,
+    slots: SlotTable<BinomialNode<T>>,
+    _marker: PhantomData<Box<BinomialNode<T>>>,
+}
+
+unsafe impl<T: Send> Send for BinomialHeap<T> {}
+unsafe impl<T: Sync> Sync for BinomialHeap<T> {}
+
+impl<T: Ord> BinomialHeap<T> {
+    pub fn new() -> Self {
+        Self {
+            head: None,
+            len: 0,
+            slots: SlotTable::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// The smallest element.
+    pub fn peek(&self) -> Option<&T> {
+        self.min_root().map(|(min, _)| unsafe { &(*min.as_ptr()).value })
+    }
+
+    pub fn push(&mut self, value: T) -> HeapHandle {
+        let node = NonNull::from(Box::leak(Box::new(BinomialNode {
+            value,
+            parent: None,
+            child: None,
+            sibling: None,
+            degree: 0,
+            owner: self.slots.id,
+            slot: 0,
+        })));
+        let (slot, handle) = self.slots.track(node);
+        let head = self.head;
+        let rebuild = EmptyOnUnwind::new(&mut self.head, &mut self.len, &mut self.slots);
+        let new_head = unsafe {
+            (*node.as_ptr()).slot = slot;
+            Self::union(head, Some(node))
+        };
+        rebuild.disarm();
+        self.head = new_head;
+        self.len += 1;
+        handle
+    }
+
+    /// Removes the smallest element. If `Ord` panics while the trees are
+    /// rebuilt, the heap is left empty and its elements leak.
+    pub fn pop(&mut self) -> Option<T> {
+        let (min, before) = self.min_root()?;
+        let mut head = self.head;
+        let rebuild = EmptyOnUnwind::new(&mut self.head, &mut self.len, &mut self.slots);
+        let new_head = unsafe {
+            match before {
+                None => head = (*min.as_ptr()).sibling,
+                Some(before) => (*before.as_ptr()).sibling = (*min.as_ptr()).sibling,
+            }
+            // the children are ordered by decreasing degree, so reverse them
+            let mut children = None;
+            let mut child = (*min.as_ptr()).child;
+            while let Some(node) = child {
+                child = (*node.as_ptr()).sibling;
+                (*node.as_ptr()).sibling = children;
+                (*node.as_ptr()).parent = None;
+                children = Some(node);
+            }
+            Self::union(head, children)
+        };
+        rebuild.disarm();
+
+        self.head = new_head;
+        let node = unsafe { Box::from_raw(min.as_ptr()) };
+        self.slots.release(node.owner, node.slot);
+        self.len -= 1;
+        Some(node.value)
+    }
+
+    /// Moves every element of `other` into `self` in O(log n), leaving
+    /// `other` empty. Handles into `other` become stale. If `Ord` panics,
+    /// both heaps are left empty.
+    pub fn meld(&mut self, other: &mut Self) {
+        let other_head = other.head.take();
+        let other_len = mem::take(&mut other.len);
+        other.slots.reset();
+        let head = self.head;
+        let rebuild = EmptyOnUnwind::new(&mut self.head, &mut self.len, &mut self.slots);
+        let new_head = unsafe { Self::union(head, other_head) };
+        rebuild.disarm();
+        self.head = new_head;
+        self.len += other_len;
+    }
+
+    pub fn get(&self, handle: HeapHandle) -> Option<&T> {
+        self.slots.get(handle).map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    pub fn contains(&self, handle: HeapHandle) -> bool {
+        self.slots.get(handle).is_some()
+    }
+
+    /// Replaces the element behind `handle` with a smaller (or equal) one.
+    ///
+    /// If `Ord` panics while the element moves up, the heap keeps all of its
+    /// elements and handles, but may pop them in an unspecified order.
+    pub fn decrease_key(&mut self, handle: HeapHandle, value: T) -> Result<(), CollectionError> {
+        let node = self.slots.get(handle).ok_or(CollectionError::InvalidHandle {
+            operation: "decrease_key",
+        })?;
+        unsafe {
+            if value > (*node.as_ptr()).value {
+                return Err(CollectionError::KeyIncrease { operation: "decrease_key" });
+            }
+            (*node.as_ptr()).value = value;
+
+            // bubble the element up, taking its slot along so handles follow it
+            let mut current = node;
+            while let Some(parent) = (*current.as_ptr()).parent {
+                if (*current.as_ptr()).value >= (*parent.as_ptr()).value {
+                    break;
+                }
+                let (current_ref, parent_ref) = (&mut *current.as_ptr(), &mut *parent.as_ptr());
+                mem::swap(&mut current_ref.value, &mut parent_ref.value);
+                mem::swap(&mut current_ref.owner, &mut parent_ref.owner);
+                mem::swap(&mut current_ref.slot, &mut parent_ref.slot);
+                self.slots.relocate(current_ref.owner, current_ref.slot, current);
+                self.slots.relocate(parent_ref.owner, parent_ref.slot, parent);
+                current = parent;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    // The root holding the smallest element, and the root before it.
+    fn min_root(&self) -> Option<RootEntry<T>> {
+        let mut min = self.head?;
+        let mut min_before = None;
+        let mut before = min;
+        unsafe {
+            while let Some(node) = (*before.as_ptr()).sibling {
+                if (*node.as_ptr()).value < (*min.as_ptr()).value {
+                    min = node;
+                    min_before = Some(before);
+                }
+                before = node;
+            }
+        }
+        Some((min, min_before))
+    }
+
+    // Merges two root lists and links roots of equal degree, like adding two
+    // binary numbers.
+    unsafe fn union(
+        a: Option<NonNull<BinomialNode<T>>>,
+        b: Option<NonNull<BinomialNode<T>>>,
+    ) -> Option<NonNull<BinomialNode<T>>> {
+        let mut head = Self::merge_roots(a, b);
+        let mut current = head?;
+        let mut before: Option<NonNull<BinomialNode<T>>> = None;
+        while let Some(next) = (*current.as_ptr()).sibling {
+            let after = (*next.as_ptr()).sibling;
+            let degree = (*current.as_ptr()).degree;
+            if degree != (*next.as_ptr()).degree
+                || after.is_some_and(|after| (*after.as_ptr()).degree == degree)
+            {
+                before = Some(current);
+                current = next;
+            } else if (*current.as_ptr()).value <= (*next.as_ptr()).value {
+                (*current.as_ptr()).sibling = after;
+                Self::link(next, current);
+            } else {
+                match before {
+                    None => head = Some(next),
+                    Some(before) => (*before.as_ptr()).sibling = Some(next),
+                }
+                Self::link(current, next);
+                current = next;
+            }
+        }
+        head
+    }
+
+    // Merges two root lists by degree, without linking any trees.
+    unsafe fn merge_roots(
+        mut a: Option<NonNull<BinomialNode<T>>>,
+        mut b: Option<NonNull<BinomialNode<T>>>,
+    ) -> Option<NonNull<BinomialNode<T>>> {
+        let mut head = None;
+        let mut tail: Option<NonNull<BinomialNode<T>>> = None;
+        loop {
+            let node = match (a, b) {
+                (Some(x), Some(y)) => {
+                    if (*x.as_ptr()).degree <= (*y.as_ptr()).degree {
+                        a = (*x.as_ptr()).sibling;
+                        x
+                    } else {
+                        b = (*y.as_ptr()).sibling;
+                        y
+                    }
+                }
+                (rest, None) | (None, rest) => {
+                    match tail {
+                        None => head = rest,
+                        Some(tail) => (*tail.as_ptr()).sibling = rest,
+                    }
+                    return head;
+                }
+            };
+            match tail {
+                None => head = Some(node),
+                Some(tail) => (*tail.as_ptr()).sibling = Some(node),
+            }
+            tail = Some(node);
+        }
+    }
+
+    // Makes `child` the first child of `parent`; both have the same degree.
+    unsafe fn link(child: NonNull<BinomialNode<T>>, parent: NonNull<BinomialNode<T>>) {
+        (*child.as_ptr()).parent = Some(parent);
+        (*child.as_ptr()).sibling = (*parent.as_ptr()).child;
+        (*parent.as_ptr()).child = Some(child);
+        (*parent.as_ptr()).degree += 1;
+    }
+}
+
+impl<T> Drop for BinomialHeap<T> {
+    fn drop(&mut self) {
+        let mut pending: Vec<NonNull<BinomialNode<T>>> = self.head.take().into_iter().collect();
+        while let Some(node) = pending.pop() {
+            let node = unsafe { Box::from_raw(node.as_ptr()) };
+            pending.extend(node.child);
+            pending.extend(node.sibling);
+        }
+    }
+}
+
+impl<T: Ord> Default for BinomialHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> Extend<T> for BinomialHeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T: Ord> FromIterator<T> for BinomialHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = Self::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BinomialHeap, HeapHandle, PairingHeap};
+    use crate::project_errors::CollectionError;
+
+    // The shared surface of both heaps, so every test runs against each.
+    trait MeldableHeap: Default {
+        fn push(&mut self, value: String) -> HeapHandle;
+        fn pop(&mut self) -> Option<String>;
+        fn peek(&self) -> Option<&String>;
+        fn len(&self) -> usize;
+        fn meld(&mut self, other: &mut Self);
+        fn get(&self, handle: HeapHandle) -> Option<&String>;
+        fn decrease_key(&mut self, handle: HeapHandle, value: String) -> Result<(), CollectionError>;
+    }
+
+    macro_rules! meldable_heap {
+        ($heap:ident) => {
+            impl MeldableHeap for $heap<String> {
+                fn push(&mut self, value: String) -> HeapHandle {
+                    $heap::push(self, value)
+                }
+                fn pop(&mut self) -> Option<String> {
+                    $heap::pop(self)
+                }
+                fn peek(&self) -> Option<&String> {
+                    $heap::peek(self)
+                }
+                fn len(&self) -> usize {
+                    $heap::len(self)
+                }
+                fn meld(&mut self, other: &mut Self) {
+                    $heap::meld(self, other)
+                }
+                fn get(&self, handle: HeapHandle) -> Option<&String> {
+                    $heap::get(self, handle)
+                }
+                fn decrease_key(&mut self, handle: HeapHandle, value: String) -> Result<(), CollectionError> {
+                    $heap::decrease_key(self, handle, value)
+                }
+            }
+        };
+    }
+
+    meldable_heap!(PairingHeap);
+    meldable_heap!(BinomialHeap);
+
+    // Zero-padded so that string order matches numeric order.
+    fn key(n: i32) -> String {
+        format!("{:03}", n)
+    }
+
+    fn drain<H: MeldableHeap>(heap: &mut H) -> Vec<String> {
+        std::iter::from_fn(|| heap.pop()).collect()
+    }
+
+    fn pops_in_order<H: MeldableHeap>() {
+        let mut heap = H::default();
+        assert_eq!(heap.pop(), None);
+        let values: Vec<i32> = (0..40).map(|i| (i * 17) % 41).collect();
+        for &v in &values {
+            heap.push(key(v));
+        }
+        heap.push(key(5));
+        assert_eq!(heap.len(), 41);
+        assert_eq!(heap.peek(), Some(&key(0)));
+
+        let mut expected: Vec<String> = values.iter().map(|&v| key(v)).collect();
+        expected.push(key(5));
+        expected.sort();
+        assert_eq!(drain(&mut heap), expected);
+        assert_eq!(heap.len(), 0);
+    }
+
+    fn melds_and_keeps_own_handles<H: MeldableHeap>() {
+        let mut left = H::default();
+        let mut right = H::default();
+        let left_handles: Vec<_> = (0..10).map(|i| left.push(key(10 + i * 2))).collect();
+        let right_handles: Vec<_> = (0..7).map(|i| right.push(key(10 + i * 3))).collect();
+        left.pop();
+
+        left.meld(&mut right);
+        assert_eq!(left.len(), 16);
+        assert_eq!(right.len(), 0);
+        assert_eq!(left.get(left_handles[4]), Some(&key(18)));
+        assert_eq!(left.get(left_handles[0]), None);
+        assert_eq!(left.get(right_handles[1]), None);
+        assert_eq!(right.get(right_handles[1]), None);
+
+        // the melded-away heap is empty but still usable
+        let handle = right.push(key(1));
+        assert_eq!(right.get(handle), Some(&key(1)));
+        left.meld(&mut H::default());
+
+        assert_eq!(left.decrease_key(left_handles[9], key(1)), Ok(()));
+        let mut expected: Vec<String> = (1..9).map(|i| key(10 + i * 2)).chain((0..7).map(|i| key(10 + i * 3))).collect();
+        expected.sort();
+        expected.insert(0, key(1));
+        assert_eq!(drain(&mut left), expected);
+    }
+
+    fn decreases_keys<H: MeldableHeap>() {
+        let mut heap = H::default();
+        let handles: Vec<_> = (0..32).map(|i| heap.push(key(100 + i))).collect();
+        heap.pop();
+        for (i, &handle) in handles.iter().enumerate().skip(1).step_by(3) {
+            assert_eq!(heap.decrease_key(handle, key(i as i32)), Ok(()));
+        }
+        // an unchanged key is allowed and the root can be decreased too
+        assert_eq!(heap.decrease_key(handles[1], key(1)), Ok(()));
+        assert_eq!(heap.decrease_key(handles[1], key(0)), Ok(()));
+        assert_eq!(heap.get(handles[31]), Some(&key(31)));
+
+        assert_eq!(
+            heap.decrease_key(handles[2], key(999)),
+            Err(CollectionError::KeyIncrease { operation: "decrease_key" })
+        );
+        assert_eq!(
+            heap.decrease_key(handles[0], key(0)),
+            Err(CollectionError::InvalidHandle { operation: "decrease_key" })
+        );
+        let foreign = H::default().push(key(0));
+        assert_eq!(heap.get(foreign), None);
+
+        let mut expected: Vec<String> = (1..32)
+            .map(|i| if i % 3 == 1 { key(i) } else { key(100 + i) })
+            .collect();
+        expected[0] = key(0);
+        expected.sort();
+        assert_eq!(drain(&mut heap), expected);
+    }
+
+    #[test]
+    fn heaps_pop_in_order() {
+        pops_in_order::<PairingHeap<String>>();
+        pops_in_order::<BinomialHeap<String>>();
+    }
+
+    #[test]
+    fn heaps_meld() {
+        melds_and_keeps_own_handles::<PairingHeap<String>>();
+        melds_and_keeps_own_handles::<BinomialHeap<String>>();
+    }
+
+    #[test]
+    fn heaps_decrease_keys() {
+        decreases_keys::<PairingHeap<String>>();
+        decreases_keys::<BinomialHeap<String>>();
+    }
+
+    #[test]
+    fn melded_handles_follow_decrease_key() {
+        // after a meld, bubbling swaps nodes owned by different tables
+        let mut heap: BinomialHeap<String> = (0..8).map(|i| key(100 + i * 10)).collect();
+        let mut other = BinomialHeap::new();
+        let handles: Vec<_> = (0..8).map(|i| other.push(key(105 + i * 10))).collect();
+        other.meld(&mut heap);
+        for (i, &handle) in handles.iter().enumerate().rev() {
+            other.decrease_key(handle, key(i as i32)).unwrap();
+            assert_eq!(other.get(handle), Some(&key(i as i32)));
+        }
+        assert_eq!(other.peek(), Some(&key(0)));
+        assert_eq!(other.len(), 16);
+        assert_eq!(other.pop(), Some(key(0)));
+        assert!(!other.contains(handles[0]) && other.contains(handles[1]));
+    }
+
+    thread_local! {
+        static COMPARISONS_LEFT: std::cell::Cell<usize> = const { std::cell::Cell::new(usize::MAX) };
+    }
+
+    // Boxed so that a double free or a use after free shows up under Miri.
+    #[derive(Debug, PartialEq, Eq)]
+    struct Touchy(Box<u32>);
+
+    impl PartialOrd for Touchy {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Touchy {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            if COMPARISONS_LEFT.with(|left| left.replace(left.get().saturating_sub(1))) == 0 {
+                panic!("comparison failed");
+            }
+            self.0.cmp(&other.0)
+        }
+    }
+
+    // Runs `f`, which must panic within its first `comparisons + 1` comparisons.
+    fn panicking_after<R>(comparisons: usize, f: impl FnOnce() -> R) {
+        COMPARISONS_LEFT.with(|left| left.set(comparisons));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+        COMPARISONS_LEFT.with(|left| left.set(usize::MAX));
+        assert!(result.is_err());
+    }
+
+    fn panicking<R>(f: impl FnOnce() -> R) {
+        panicking_after(0, f);
+    }
+
+    macro_rules! survives_panicking_ord {
+        ($heap:ident) => {{
+            fn check(heap: &mut $heap<Touchy>) {
+                let len = heap.len();
+                let mut popped = Vec::new();
+                while let Some(Touchy(value)) = heap.pop() {
+                    popped.push(*value);
+                }
+                assert_eq!(popped.len(), len);
+                assert!(popped.windows(2).all(|pair| pair[0] <= pair[1]));
+            }
+            // odd, so a binomial push has to link the order-0 trees
+            let filled = || (0..21).map(|i| Touchy(Box::new((i * 8) % 21))).collect::<$heap<_>>();
+
+            let mut heap = filled();
+            heap.pop();
+            let handle = heap.push(Touchy(Box::new(50)));
+            panicking(|| heap.pop());
+            // either untouched (the panic came before any relinking) or emptied
+            assert!(!heap.contains(handle) || heap.get(handle) == Some(&Touchy(Box::new(50))));
+            check(&mut heap);
+            let handle = heap.push(Touchy(Box::new(3)));
+            assert_eq!(heap.get(handle), Some(&Touchy(Box::new(3))));
+
+            let mut heap = filled();
+            panicking(|| heap.push(Touchy(Box::new(1))));
+            check(&mut heap);
+
+            let mut heap = filled();
+            let handle = heap.push(Touchy(Box::new(40)));
+            panicking(|| heap.decrease_key(handle, Touchy(Box::new(0))));
+            check(&mut heap);
+
+            let mut heap = filled();
+            let mut other = filled();
+            panicking(|| heap.meld(&mut other));
+            check(&mut heap);
+            check(&mut other);
+        }};
+    }
+
+    // A panic in the middle of a rebuild leaks the heap's nodes on purpose, so
+    // Miri's leak check would fail this test.
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn panicking_ord_leaves_heaps_usable() {
+        survives_panicking_ord!(PairingHeap);
+        survives_panicking_ord!(BinomialHeap);
+    }
+
+    #[test]
+    fn panicking_ord_during_binomial_decrease_key_keeps_every_element() {
+        let mut heap: BinomialHeap<Touchy> = (0..16).map(|i| Touchy(Box::new(10 + i))).collect();
+        let handles: Vec<_> = (0..16).map(|i| heap.push(Touchy(Box::new(40 + i)))).collect();
+        // the key check and one step up succeed, the next step panics
+        panicking_after(2, || heap.decrease_key(handles[15], Touchy(Box::new(0))));
+        assert_eq!(heap.len(), 32);
+        assert!(handles.iter().all(|&handle| heap.contains(handle)));
+        let mut popped: Vec<u32> = std::iter::from_fn(|| heap.pop()).map(|Touchy(value)| *value).collect();
+        popped.sort();
+        let mut expected: Vec<u32> = (10..26).chain(40..55).collect();
+        expected.push(0);
+        expected.sort();
+        assert_eq!(popped, expected);
+    }
+}
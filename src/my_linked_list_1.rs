@@ -2,14 +2,30 @@
 // Created: Feb 23, 2025
 // Description: Linked list
 
-use std::{error::Error, fmt::Debug, marker::PhantomData, mem, ptr::NonNull};
+use std::{
+    cmp::Ordering as CmpOrdering,
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    mem,
+    ops::{Index, IndexMut},
+    ptr::NonNull,
+    sync::atomic::{AtomicU64, Ordering},
+};
 
-use crate::project_errors::OutOfIndexError;
+use crate::project_errors::CollectionError;
+
+// Slot index of a node that no handle refers to.
+const UNTRACKED: usize = usize::MAX;
+
+// Every list gets its own id so handles from another list are rejected.
+static NEXT_LIST_ID: AtomicU64 = AtomicU64::new(0);
 
 struct Node<T> {
     data: T,
     next: Option<NonNull<Node<T>>>,
     prev: Option<NonNull<Node<T>>>,
+    slot: usize,
 }
 
 impl<T> Node<T> {
@@ -18,37 +34,156 @@ impl<T> Node<T> {
             data: value,
             next: None,
             prev: None,
+            slot: UNTRACKED,
         }
     }
 
-    fn into_value(self: Box<Self>) -> T {
+    fn into_value(self) -> T {
         self.data
     }
 }
 
+/// An opaque reference to an element of a `MyLinkedList`.
+///
+/// Handles are checked against the list and a per-slot generation on every
+/// use, so a handle whose element has been removed (or that belongs to another
+/// list) is rejected instead of touching freed memory.
+///
+/// The handle accessors are `get_by_handle`, `get_by_handle_mut` and
+/// `remove_by_handle`; the plain `get`, `get_mut` and `remove` take an index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeHandle {
+    list_id: u64,
+    slot: usize,
+    generation: u64,
+}
+
+// A detached chain of nodes as `(head, tail, length)`.
+type Chain<T> = (NonNull<Node<T>>, NonNull<Node<T>>, usize);
+
+struct Slot<T> {
+    generation: u64,
+    node: Option<NonNull<Node<T>>>,
+}
+
+// A merge in progress. Dropping it, normally or during a panic in the
+// comparator, joins the merged prefix and both remainders into `out`.
+struct MergeChains<'a, T> {
+    head: Option<NonNull<Node<T>>>,
+    tail: Option<NonNull<Node<T>>>,
+    left: Option<NonNull<Node<T>>>,
+    right: Option<NonNull<Node<T>>>,
+    out: &'a mut Option<NonNull<Node<T>>>,
+}
+
+impl<T> Drop for MergeChains<'_, T> {
+    fn drop(&mut self) {
+        let rest = concat_chains(self.left.take(), self.right.take());
+        match self.tail {
+            Some(tail) => unsafe { (*tail.as_ptr()).next = rest },
+            None => self.head = rest,
+        }
+        *self.out = self.head;
+    }
+}
+
+// Holds the nodes of a list while they are relinked outside of it. On drop,
+// every chain it holds is joined and put back, so a panicking comparator
+// can neither lose nodes nor leave the list pointing at them.
+struct RelinkGuard<'a, T> {
+    list: &'a mut MyLinkedList<T>,
+    size: usize,
+    // runs[i] is empty or a sorted run of 2^i nodes; higher runs hold earlier nodes
+    runs: [Option<NonNull<Node<T>>>; usize::BITS as usize],
+    carry: Option<NonNull<Node<T>>>,
+    // not yet looked at
+    rest: Option<NonNull<Node<T>>>,
+}
+
+impl<'a, T> RelinkGuard<'a, T> {
+    // Takes every node out of `list` as the `rest` chain.
+    fn new(list: &'a mut MyLinkedList<T>) -> Self {
+        list.tail = None;
+        Self {
+            size: mem::take(&mut list.size),
+            rest: list.head.take(),
+            list,
+            runs: [None; usize::BITS as usize],
+            carry: None,
+        }
+    }
+}
+
+impl<T> Drop for RelinkGuard<'_, T> {
+    fn drop(&mut self) {
+        let mut chain = concat_chains(self.carry.take(), self.rest.take());
+        for run in self.runs.iter_mut() {
+            chain = concat_chains(run.take(), chain);
+        }
+        self.list.relink_chain(chain);
+        self.list.size = self.size;
+    }
+}
+
+// Joins two chains linked through `next`, walking the first one.
+fn concat_chains<T>(
+    first: Option<NonNull<Node<T>>>,
+    second: Option<NonNull<Node<T>>>,
+) -> Option<NonNull<Node<T>>> {
+    let Some(mut last) = first else {
+        return second;
+    };
+    unsafe {
+        while let Some(next) = last.as_ref().next {
+            last = next;
+        }
+        (*last.as_ptr()).next = second;
+    }
+    first
+}
+
 pub struct MyLinkedList<T> {
     size: usize,
     head: Option<NonNull<Node<T>>>,
     tail: Option<NonNull<Node<T>>>,
+    list_id: u64,
+    slots: Vec<Slot<T>>,
+    free_slots: Vec<usize>,
     _marker: PhantomData<Box<T>>,
 }
 
+// The list owns its nodes like a `Box` would, so it is as thread-safe as `T`.
+unsafe impl<T: Send> Send for MyLinkedList<T> {}
+unsafe impl<T: Sync> Sync for MyLinkedList<T> {}
+
 impl<T> MyLinkedList<T> {
     pub fn new() -> Self {
         Self {
             size: 0,
             head: None,
             tail: None,
+            list_id: NEXT_LIST_ID.fetch_add(1, Ordering::Relaxed),
+            slots: Vec::new(),
+            free_slots: Vec::new(),
             _marker: PhantomData,
         }
     }
 
+    // A list owning an already linked chain of `size` untracked nodes.
+    fn from_parts(size: usize, head: Option<NonNull<Node<T>>>, tail: Option<NonNull<Node<T>>>) -> Self {
+        let mut list = Self::new();
+        list.size = size;
+        list.head = head;
+        list.tail = tail;
+        list
+    }
+
     pub fn size(&self) -> usize {
         self.size
     }
 
     pub fn is_empty(&self) -> bool {
-        self.head == None
+        self.head.is_none()
     }
 
     pub fn push_front(&mut self, value: T) {
@@ -86,6 +221,7 @@ impl<T> MyLinkedList<T> {
     pub fn pop_front(&mut self) -> Option<T> {
         self.head.map(|content| {
             self.size -= 1;
+            self.release_slot(content);
             unsafe {
                 let transfer_back_node = Box::from_raw(content.as_ptr());
                 self.head = transfer_back_node.next;
@@ -100,6 +236,7 @@ impl<T> MyLinkedList<T> {
 
     pub fn pop_back(&mut self) -> Option<T> {
         self.tail.map(|content| unsafe {
+            self.release_slot(content);
             let transfer_back_node = Box::from_raw(content.as_ptr());
             self.tail = transfer_back_node.prev;
 
@@ -137,169 +274,608 @@ impl<T> MyLinkedList<T> {
         unsafe { self.tail.as_mut().map(|node| &mut node.as_mut().data) }
     }
 
-    pub fn get_by_index(&self, idx: usize) -> Result<Option<&T>, Box<dyn Error>> {
+    /// Returns the element at `idx`, walking from whichever end is nearer.
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        self.node_at(idx).map(|node| unsafe { &(*node.as_ptr()).data })
+    }
+
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+        self.node_at(idx).map(|node| unsafe { &mut (*node.as_ptr()).data })
+    }
+
+    /// Inserts `data` so that it ends up at `idx`; `idx == len` appends.
+    pub fn insert(&mut self, idx: usize, data: T) -> Result<(), CollectionError> {
         let len = self.size;
-        if idx >= len {
-            return Err(Box::new(OutOfIndexError {}));
-        }
-
-        let offset_from_end = len - idx - 1;
-        let mut cur;
-        if idx <= offset_from_end {
-            cur = self.head;
-            for _ in 0..idx {
-                match cur.take() {
-                    None => {
-                        cur = self.head;
-                    }
-                    Some(content) => unsafe {
-                        cur = content.as_ref().next;
-                    },
-                }
+        if idx > len {
+            return Err(CollectionError::IndexOutOfRange {
+                operation: "insert",
+                index: idx,
+                len,
+            });
+        }
+
+        let next = self.node_at(idx);
+        let prev = match next {
+            None => self.tail,
+            Some(node) => unsafe { node.as_ref().prev },
+        };
+        let new_node = NonNull::from(Box::leak(Box::new(Node::new(data))));
+        self.splice_nodes(prev, next, new_node, new_node, 1);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, idx: usize) -> Result<T, CollectionError> {
+        let node = self.node_at(idx).ok_or(CollectionError::IndexOutOfRange {
+            operation: "remove",
+            index: idx,
+            len: self.size,
+        })?;
+        Ok(self.take_node(node))
+    }
+
+    #[inline]
+    fn unlink_node(&mut self, mut node: NonNull<Node<T>>) {
+        let node = unsafe {
+            node.as_mut()
+        };
+        match node.prev {
+            Some(prev) => unsafe {
+                (*prev.as_ptr()).next = node.next
+            },
+            None => {
+                self.head = node.next
+            },
+        }
+
+        match node.next {
+            Some(next) => unsafe {
+                (*next.as_ptr()).prev = node.prev
+            },
+            None => {
+                self.tail = node.prev
             }
-        } else {
-            cur = self.tail;
-            for _ in 0..idx {
-                match cur.take() {
-                    None => {
-                        cur = self.tail;
-                    }
-                    Some(content) => unsafe {
-                        cur = content.as_ref().prev;
-                    },
-                }
+        }
+
+        self.size -= 1;
+    }
+
+    // Links the already chained nodes `splice_start..=splice_end` in between
+    // `existing_prev` and `existing_next`, which must be adjacent (or the list ends).
+    #[inline]
+    fn splice_nodes(
+        &mut self,
+        existing_prev: Option<NonNull<Node<T>>>,
+        existing_next: Option<NonNull<Node<T>>>,
+        splice_start: NonNull<Node<T>>,
+        splice_end: NonNull<Node<T>>,
+        splice_length: usize,
+    ) {
+        unsafe {
+            match existing_prev {
+                Some(prev) => (*prev.as_ptr()).next = Some(splice_start),
+                None => self.head = Some(splice_start),
+            }
+            match existing_next {
+                Some(next) => (*next.as_ptr()).prev = Some(splice_end),
+                None => self.tail = Some(splice_end),
             }
+            (*splice_start.as_ptr()).prev = existing_prev;
+            (*splice_end.as_ptr()).next = existing_next;
         }
-        unsafe { Ok(cur.as_ref().map(|content| &content.as_ref().data)) }
+
+        self.size += splice_length;
     }
-    
-    fn _get_by_index_mut(&self, idx: usize) -> Result<Option<NonNull<Node<T>>>, Box<dyn Error>> {
-        let len = self.size;
-        if idx > len {
-            return Err(Box::new(OutOfIndexError {}));
-        }
-
-        let offset_from_end = len - idx - 1;
-        let offset_from_start = idx;
-        let mut cur;
-
-        if offset_from_start <= offset_from_end {
-            cur = self.head;
-            for _ in 0..idx {
-                match cur.take() {
-                    None => { cur = self.head },
-                    Some(node) => { 
-                        unsafe {
-                            cur = node.as_ref().next;
-                        }
+
+    // Splits the list after `split_node`, which sits at position `at - 1`.
+    // A `None` node splits off the whole list.
+    fn split_off_after_node(&mut self, split_node: Option<NonNull<Node<T>>>, at: usize) -> Self {
+        match split_node {
+            None => self.split_off_all(),
+            Some(split_node) => unsafe {
+                let second_head = (*split_node.as_ptr()).next.take();
+                let second_tail = match second_head {
+                    None => None,
+                    Some(head) => {
+                        (*head.as_ptr()).prev = None;
+                        self.tail
                     }
-                }
-            }
-        } else {
-            cur = self.tail;
-            for _ in 0..idx {
-                match cur.take() {
-                    None => { cur = self.tail },
-                    Some(node) => {
-                        unsafe {
-                            cur = node.as_ref().prev;
-                        }
+                };
+
+                let second_part = Self::from_parts(self.size - at, second_head, second_tail);
+
+                self.tail = Some(split_node);
+                self.size = at;
+                self.release_chain(second_part.head);
+                second_part
+            },
+        }
+    }
+
+    // Splits the list before `split_node`, which sits at position `at`.
+    // A `None` node splits off the whole list.
+    fn split_off_before_node(&mut self, split_node: Option<NonNull<Node<T>>>, at: usize) -> Self {
+        match split_node {
+            None => self.split_off_all(),
+            Some(split_node) => unsafe {
+                let first_tail = (*split_node.as_ptr()).prev.take();
+                let first_head = match first_tail {
+                    None => None,
+                    Some(tail) => {
+                        (*tail.as_ptr()).next = None;
+                        self.head
                     }
+                };
+
+                let first_part = Self::from_parts(at, first_head, first_tail);
+
+                self.head = Some(split_node);
+                self.size -= at;
+                self.release_chain(first_part.head);
+                first_part
+            },
+        }
+    }
+
+    // The node at position `idx`, walking from whichever end is nearer.
+    fn node_at(&self, idx: usize) -> Option<NonNull<Node<T>>> {
+        if idx >= self.size {
+            return None;
+        }
+        let offset_from_end = self.size - idx - 1;
+        unsafe {
+            if idx <= offset_from_end {
+                let mut cur = self.head?;
+                for _ in 0..idx {
+                    cur = cur.as_ref().next?;
+                }
+                Some(cur)
+            } else {
+                let mut cur = self.tail?;
+                for _ in 0..offset_from_end {
+                    cur = cur.as_ref().prev?;
                 }
+                Some(cur)
             }
         }
+    }
+
+    // Detaches every node of `other`, releasing its handles.
+    fn take_chain(other: &mut Self) -> Option<Chain<T>> {
+        let mut all = other.split_off_all();
+        let chain = (all.head.take()?, all.tail.take()?, mem::take(&mut all.size));
+        Some(chain)
+    }
+
+    // Moves every node into a new list; handles into this list are invalidated.
+    fn split_off_all(&mut self) -> Self {
+        let all = Self::from_parts(mem::take(&mut self.size), self.head.take(), self.tail.take());
+        self.reset_slots();
+        all
+    }
 
-        Ok(cur)
-        
+    // Invalidates every handle in O(1) by giving the list a fresh id. The
+    // nodes keep their old slot numbers, which `release_slot` ignores.
+    fn reset_slots(&mut self) {
+        self.list_id = NEXT_LIST_ID.fetch_add(1, Ordering::Relaxed);
+        self.slots.clear();
+        self.free_slots.clear();
     }
 
-    pub fn get_by_index_mut(&self, idx: usize) -> Result<Option<&mut T>, Box<dyn Error>>{
-        let mut cur = self._get_by_index_mut(idx)?;
+    fn track_node(&mut self, node: NonNull<Node<T>>) -> NodeHandle {
+        let slot = match self.free_slots.pop() {
+            Some(slot) => {
+                self.slots[slot].node = Some(node);
+                slot
+            }
+            None => {
+                self.slots.push(Slot { generation: 0, node: Some(node) });
+                self.slots.len() - 1
+            }
+        };
         unsafe {
-            Ok(cur.as_mut().map(|node| &mut node.as_mut().data))
+            (*node.as_ptr()).slot = slot;
         }
-    }
 
-    pub fn insert_by_index(&mut self, idx: usize, data: T) -> Result<(), Box<dyn Error>> {
-        let len = self.size;
+        NodeHandle {
+            list_id: self.list_id,
+            slot,
+            generation: self.slots[slot].generation,
+        }
+    }
 
-        if idx >= len {
-            return Err(Box::new(OutOfIndexError {}));
+    // Must be called whenever a node leaves the list, so its handle goes stale.
+    // A slot the node kept from a list it was moved out of is not ours.
+    #[inline]
+    fn release_slot(&mut self, node: NonNull<Node<T>>) {
+        let slot = unsafe { mem::replace(&mut (*node.as_ptr()).slot, UNTRACKED) };
+        if let Some(entry) = self.slots.get_mut(slot).filter(|entry| entry.node == Some(node)) {
+            entry.generation += 1;
+            entry.node = None;
+            self.free_slots.push(slot);
         }
+    }
 
-        if idx == 0 {
-            return Ok(self.push_front(data) )
-        } else if idx == len {
-            return Ok(self.push_back(data))
+    // Releases the slots of a chain of nodes that was detached from this list.
+    // O(1) while no handle is live, otherwise O(length of the chain).
+    fn release_chain(&mut self, mut cur: Option<NonNull<Node<T>>>) {
+        if self.free_slots.len() == self.slots.len() {
+            return;
+        }
+        while let Some(node) = cur {
+            self.release_slot(node);
+            cur = unsafe { node.as_ref().next };
         }
+    }
 
-        unsafe {
-            let mut new_node = Box::new(Node::new(data));
-            let helper_before = self._get_by_index_mut(idx -1)?;
-            let helper_after = helper_before.unwrap().as_mut().next;
-            new_node.next = helper_after;
-            new_node.prev = helper_before;
-            let new_node = NonNull::new(Box::into_raw(new_node));
-            helper_before.unwrap().as_mut().next = new_node;
-            helper_after.unwrap().as_mut().prev = new_node;
+    fn handle_node(&self, handle: NodeHandle) -> Option<NonNull<Node<T>>> {
+        if handle.list_id != self.list_id {
+            return None;
         }
+        let slot = self.slots.get(handle.slot)?;
+        if slot.generation != handle.generation {
+            return None;
+        }
+        slot.node
+    }
 
-        self.size += 1;
+    /// Like `push_front`, but returns a handle to the new element.
+    pub fn push_front_handle(&mut self, value: T) -> NodeHandle {
+        self.push_front(value);
+        let head = self.head.expect("List is empty right after push_front.");
+        self.track_node(head)
+    }
+
+    /// Like `push_back`, but returns a handle to the new element.
+    pub fn push_back_handle(&mut self, value: T) -> NodeHandle {
+        self.push_back(value);
+        let tail = self.tail.expect("List is empty right after push_back.");
+        self.track_node(tail)
+    }
+
+    /// Whether `handle` still refers to an element of this list.
+    pub fn contains_handle(&self, handle: NodeHandle) -> bool {
+        self.handle_node(handle).is_some()
+    }
+
+    pub fn get_by_handle(&self, handle: NodeHandle) -> Option<&T> {
+        unsafe { self.handle_node(handle).map(|node| &(*node.as_ptr()).data) }
+    }
+
+    pub fn get_by_handle_mut(&mut self, handle: NodeHandle) -> Option<&mut T> {
+        unsafe { self.handle_node(handle).map(|node| &mut (*node.as_ptr()).data) }
+    }
+
+    /// Removes the element behind `handle` in O(1).
+    /// Returns `None` if the handle is stale.
+    pub fn remove_by_handle(&mut self, handle: NodeHandle) -> Option<T> {
+        let node = self.handle_node(handle)?;
+        Some(self.take_node(node))
+    }
 
+    pub fn move_to_front(&mut self, handle: NodeHandle) -> Result<(), CollectionError> {
+        let node = self.handle_node(handle).ok_or(CollectionError::InvalidHandle {
+            operation: "move_to_front",
+        })?;
+        if self.head != Some(node) {
+            self.unlink_node(node);
+            self.splice_nodes(None, self.head, node, node, 1);
+        }
         Ok(())
     }
 
-    pub fn remove_by_idx(&mut self, idx: usize) -> Result<T, Box<dyn Error>> {
-        let len = self.size;
-        if idx >= len {
-            return Err(Box::new(OutOfIndexError {}));
+    pub fn move_to_back(&mut self, handle: NodeHandle) -> Result<(), CollectionError> {
+        let node = self.handle_node(handle).ok_or(CollectionError::InvalidHandle {
+            operation: "move_to_back",
+        })?;
+        if self.tail != Some(node) {
+            self.unlink_node(node);
+            self.splice_nodes(self.tail, None, node, node, 1);
+        }
+        Ok(())
+    }
+
+    /// Moves all elements of `other` to the back of this list in O(1),
+    /// leaving `other` empty. Handles into `other` are invalidated.
+    pub fn append(&mut self, other: &mut Self) {
+        if let Some((head, tail, len)) = Self::take_chain(other) {
+            self.splice_nodes(self.tail, None, head, tail, len);
         }
+    }
 
-        if idx == 0 {
-            return Ok(self.pop_front().unwrap());
-        } else if idx == len {
-            return Ok(self.pop_back().unwrap());
+    /// Moves all elements of `other` to the front of this list in O(1),
+    /// leaving `other` empty. Handles into `other` are invalidated.
+    pub fn prepend(&mut self, other: &mut Self) {
+        if let Some((head, tail, len)) = Self::take_chain(other) {
+            self.splice_nodes(None, self.head, head, tail, len);
         }
+    }
+
+    /// Splits the list in two at `at`, returning everything from `at` on.
+    /// Handles into the returned part are invalidated, which walks the
+    /// returned part if this list has live handles.
+    ///
+    /// Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.size, "split_off: index {} out of the range for length {}.", at, self.size);
+        let split_node = at.checked_sub(1).and_then(|idx| self.node_at(idx));
+        self.split_off_after_node(split_node, at)
+    }
 
-        let cur = self._get_by_index_mut(idx)?.unwrap();
-        self.unlink_node(cur);
+    /// Moves all elements of `other` into this list so that the first of them
+    /// ends up at `idx`, leaving `other` empty.
+    pub fn splice_at(&mut self, idx: usize, other: &mut Self) -> Result<(), CollectionError> {
+        if idx > self.size {
+            return Err(CollectionError::IndexOutOfRange {
+                operation: "splice_at",
+                index: idx,
+                len: self.size,
+            });
+        }
+        if let Some((head, tail, len)) = Self::take_chain(other) {
+            let prev = idx.checked_sub(1).and_then(|i| self.node_at(i));
+            let next = match prev {
+                Some(prev) => unsafe { prev.as_ref().next },
+                None => self.head,
+            };
+            self.splice_nodes(prev, next, head, tail, len);
+        }
+        Ok(())
+    }
 
+    // Merges two sorted chains linked through `next` into `out`, preferring
+    // `left` on ties. The `prev` links of the result are left stale.
+    fn merge_chains<F>(
+        left: Option<NonNull<Node<T>>>,
+        right: Option<NonNull<Node<T>>>,
+        compare: &mut F,
+        out: &mut Option<NonNull<Node<T>>>,
+    ) where
+        F: FnMut(&T, &T) -> CmpOrdering,
+    {
+        let mut merge = MergeChains { head: None, tail: None, left, right, out };
         unsafe {
-            let unlinked_node = Box::from_raw(cur.as_ptr());
-            Ok(unlinked_node.data)
+            while let (Some(l), Some(r)) = (merge.left, merge.right) {
+                let node = if compare(&l.as_ref().data, &r.as_ref().data) == CmpOrdering::Greater {
+                    merge.right = r.as_ref().next;
+                    r
+                } else {
+                    merge.left = l.as_ref().next;
+                    l
+                };
+                match merge.tail {
+                    Some(tail) => (*tail.as_ptr()).next = Some(node),
+                    None => merge.head = Some(node),
+                }
+                merge.tail = Some(node);
+            }
         }
     }
 
-    #[inline]
-    fn unlink_node(&mut self, mut node: NonNull<Node<T>>) {
-        let node = unsafe {
-            node.as_mut()
-        };
-        match node.prev {
-            Some(prev) => unsafe {
-                (*prev.as_ptr()).next = node.next
-            },
-            None => {
-                self.head = node.next
-            },
+    // Makes the `next` chain starting at `head` the whole list, fixing up `prev` links.
+    fn relink_chain(&mut self, head: Option<NonNull<Node<T>>>) {
+        let mut prev = None;
+        let mut cur = head;
+        while let Some(node) = cur {
+            unsafe {
+                (*node.as_ptr()).prev = prev;
+                cur = node.as_ref().next;
+            }
+            prev = Some(node);
         }
+        self.head = head;
+        self.tail = prev;
+    }
 
-        match node.next {
-            Some(next) => unsafe {
-                (*next.as_ptr()).prev = node.prev
-            },
-            None => {
-                self.tail = node.prev
+    /// Sorts the list with a stable bottom-up merge sort that relinks nodes,
+    /// so elements are never moved and handles stay valid.
+    ///
+    /// If `compare` panics, the list keeps all of its elements (and handles),
+    /// in an unspecified order.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> CmpOrdering,
+    {
+        let mut sort = RelinkGuard::new(self);
+        while let Some(node) = sort.rest {
+            sort.rest = unsafe { (*node.as_ptr()).next.take() };
+            sort.carry = Some(node);
+            let mut i = 0;
+            while let Some(run) = sort.runs[i].take() {
+                let carry = sort.carry.take();
+                Self::merge_chains(Some(run), carry, &mut compare, &mut sort.carry);
+                i += 1;
             }
+            sort.runs[i] = sort.carry.take();
         }
 
-        self.size -= 1;
+        for i in 0..sort.runs.len() {
+            if let Some(run) = sort.runs[i].take() {
+                let later = sort.carry.take();
+                Self::merge_chains(Some(run), later, &mut compare, &mut sort.carry);
+            }
+        }
+    }
+
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
+
+    pub fn is_sorted(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.iter().zip(self.iter().skip(1)).all(|(a, b)| a <= b)
+    }
+
+    /// Merges the already sorted `other` into this sorted list in a single pass,
+    /// leaving `other` empty. Equal elements from this list come first.
+    pub fn merge_sorted(&mut self, other: &mut Self)
+    where
+        T: Ord,
+    {
+        if let Some((head, _, len)) = Self::take_chain(other) {
+            let mut merge = RelinkGuard::new(self);
+            merge.size += len;
+            let mine = merge.rest.take();
+            Self::merge_chains(mine, Some(head), &mut T::cmp, &mut merge.carry);
+        }
+    }
+
+    /// Reverses the list in place by swapping the links of every node.
+    pub fn reverse(&mut self) {
+        let mut cur = self.head;
+        while let Some(node) = cur {
+            unsafe {
+                let node = &mut *node.as_ptr();
+                mem::swap(&mut node.prev, &mut node.next);
+                cur = node.prev;
+            }
+        }
+        mem::swap(&mut self.head, &mut self.tail);
+    }
+
+    /// Rotates the list `k` places to the left, so the element at `k` becomes
+    /// the front. Only the links at the seam are touched.
+    ///
+    /// Panics if `k > len`.
+    pub fn rotate_left(&mut self, k: usize) {
+        assert!(k <= self.size, "rotate_left: index {} out of the range for length {}.", k, self.size);
+        if k == 0 || k == self.size {
+            return;
+        }
+        let new_head = self.node_at(k).expect("0 < k < len, so the node exists.");
+        unsafe {
+            let new_tail = (*new_head.as_ptr()).prev.take();
+            (*new_tail.unwrap().as_ptr()).next = None;
+            (*self.tail.unwrap().as_ptr()).next = self.head;
+            (*self.head.unwrap().as_ptr()).prev = self.tail;
+            self.head = Some(new_head);
+            self.tail = new_tail;
+        }
+    }
+
+    /// Rotates the list `k` places to the right, so the last `k` elements
+    /// move to the front.
+    ///
+    /// Panics if `k > len`.
+    pub fn rotate_right(&mut self, k: usize) {
+        assert!(k <= self.size, "rotate_right: index {} out of the range for length {}.", k, self.size);
+        self.rotate_left(self.size - k);
     }
 
-    pub fn into_iter(self) -> IntoIter<T> {
-        IntoIter{
-            list: self
+    /// Exchanges the elements at `i` and `j` by relinking their nodes,
+    /// so handles follow their elements.
+    pub fn swap(&mut self, i: usize, j: usize) -> Result<(), CollectionError> {
+        let len = self.size;
+        if let Some(&index) = [i, j].iter().find(|&&index| index >= len) {
+            return Err(CollectionError::IndexOutOfRange { operation: "swap", index, len });
+        }
+        if i == j {
+            return Ok(());
+        }
+
+        let (first, second) = (i.min(j), i.max(j));
+        let a = self.node_at(first).unwrap();
+        let b = self.node_at(second).unwrap();
+        let (a_prev, a_next) = unsafe { (a.as_ref().prev, a.as_ref().next) };
+        let (b_prev, b_next) = unsafe { (b.as_ref().prev, b.as_ref().next) };
+
+        if a_next == Some(b) {
+            self.unlink_node(b);
+            self.splice_nodes(a_prev, Some(a), b, b, 1);
+        } else {
+            self.unlink_node(a);
+            self.unlink_node(b);
+            self.splice_nodes(b_prev, b_next, a, a, 1);
+            self.splice_nodes(a_prev, a_next, b, b, 1);
         }
+        Ok(())
+    }
+
+    // Unlinks `node`, releases its handle and returns its element.
+    fn take_node(&mut self, node: NonNull<Node<T>>) -> T {
+        self.unlink_node(node);
+        self.release_slot(node);
+        unsafe { Box::from_raw(node.as_ptr()).into_value() }
+    }
+
+    /// Keeps only the elements for which `keep` returns `true`, in one pass.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_mut(|elem| keep(elem));
+    }
+
+    pub fn retain_mut<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let mut cur = self.head;
+        while let Some(node) = cur {
+            unsafe {
+                cur = node.as_ref().next;
+                if !keep(&mut (*node.as_ptr()).data) {
+                    self.take_node(node);
+                }
+            }
+        }
+    }
+
+    /// Removes consecutive elements for which `same_bucket(current, previous)`
+    /// returns `true`, keeping the first of each run.
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let Some(mut kept) = self.head else { return };
+        let mut cur = unsafe { kept.as_ref().next };
+        while let Some(node) = cur {
+            unsafe {
+                cur = node.as_ref().next;
+                if same_bucket(&mut (*node.as_ptr()).data, &mut (*kept.as_ptr()).data) {
+                    self.take_node(node);
+                } else {
+                    kept = node;
+                }
+            }
+        }
+    }
+
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: PartialEq,
+        F: FnMut(&mut T) -> K,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    /// Returns an iterator that removes and yields the elements for which
+    /// `pred` returns `true`. Elements not yet visited when the iterator is
+    /// dropped stay in the list.
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        ExtractIf { next: self.head, list: self, pred }
     }
 
     pub fn iter(&self) -> Iter<'_, T> {
@@ -330,6 +906,22 @@ impl<T> MyLinkedList<T> {
     pub fn clear(&mut self) {
         *self = Self::new();
     }
+
+    pub fn cursor_front(&self) -> Cursor<'_, T> {
+        Cursor { index: 0, current: self.head, list: self }
+    }
+
+    pub fn cursor_back(&self) -> Cursor<'_, T> {
+        Cursor { index: self.size.saturating_sub(1), current: self.tail, list: self }
+    }
+
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut { index: 0, current: self.head, list: self }
+    }
+
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut { index: self.size.saturating_sub(1), current: self.tail, list: self }
+    }
 }
 
 impl<T> Default for MyLinkedList<T> {
@@ -348,6 +940,128 @@ impl<T: Debug> MyLinkedList<T> {
     }
 }
 
+impl<T> FromIterator<T> for MyLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for MyLinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for elem in iter {
+            self.push_back(elem);
+        }
+    }
+}
+
+impl<'a, T: 'a + Copy> Extend<&'a T> for MyLinkedList<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T> IntoIterator for MyLinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a MyLinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut MyLinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for MyLinkedList<T> {
+    fn from(arr: [T; N]) -> Self {
+        arr.into_iter().collect()
+    }
+}
+
+impl<T> From<Vec<T>> for MyLinkedList<T> {
+    fn from(vec: Vec<T>) -> Self {
+        vec.into_iter().collect()
+    }
+}
+
+// Clones only the elements; handles into `self` are not valid for the clone.
+impl<T: Clone> Clone for MyLinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: Debug> Debug for MyLinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T> Index<usize> for MyLinkedList<T> {
+    type Output = T;
+
+    fn index(&self, idx: usize) -> &T {
+        let len = self.size;
+        self.get(idx)
+            .unwrap_or_else(|| panic!("index: index {} out of the range for length {}.", idx, len))
+    }
+}
+
+impl<T> IndexMut<usize> for MyLinkedList<T> {
+    fn index_mut(&mut self, idx: usize) -> &mut T {
+        let len = self.size;
+        self.get_mut(idx)
+            .unwrap_or_else(|| panic!("index_mut: index {} out of the range for length {}.", idx, len))
+    }
+}
+
+impl<T: PartialEq> PartialEq for MyLinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for MyLinkedList<T> {}
+
+impl<T: PartialOrd> PartialOrd for MyLinkedList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        self.iter().partial_cmp(other)
+    }
+}
+
+impl<T: Ord> Ord for MyLinkedList<T> {
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        self.iter().cmp(other)
+    }
+}
+
+impl<T: Hash> Hash for MyLinkedList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // the length keeps [[1], [2]] and [[1, 2]] style nestings apart
+        self.size.hash(state);
+        for elem in self {
+            elem.hash(state);
+        }
+    }
+}
+
 impl<T> Drop for MyLinkedList<T> {
     fn drop(&mut self) {
         struct DropGuard<'a, T>(&'a mut MyLinkedList<T>);
@@ -363,9 +1077,7 @@ impl<T> Drop for MyLinkedList<T> {
             drop(node);
             mem::forget(guard);
         }
-
-        println!("LinkedList dropped.")
-    } 
+    }
 }
 
 pub struct IntoIter<T> {
@@ -379,6 +1091,9 @@ pub struct Iter<'a, T: 'a> {
     _marker: PhantomData<&'a Node<T>>,
 }
 
+unsafe impl<'a, T: Sync> Send for Iter<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for Iter<'a, T> {}
+
 pub struct IterMut<'a, T: 'a> {
     head: Option<NonNull<Node<T>>>,
     tail: Option<NonNull<Node<T>>>,
@@ -386,6 +1101,9 @@ pub struct IterMut<'a, T: 'a> {
     _marker: PhantomData<&'a mut Node<T>>,
 }
 
+unsafe impl<'a, T: Send> Send for IterMut<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for IterMut<'a, T> {}
+
 impl<T> Iterator for IntoIter<T> {
     type Item = T;
 
@@ -502,18 +1220,776 @@ impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
 
 impl<T> DoubleEndedIterator for IntoIter<T> {
     #[inline]
-
     fn next_back(&mut self) -> Option<Self::Item> {
         self.list.pop_back()
     }
 }
 
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+/// The iterator returned by `MyLinkedList::extract_if`.
+pub struct ExtractIf<'a, T: 'a, F> {
+    list: &'a mut MyLinkedList<T>,
+    next: Option<NonNull<Node<T>>>,
+    pred: F,
+}
+
+unsafe impl<'a, T: Send, F: Send> Send for ExtractIf<'a, T, F> {}
+unsafe impl<'a, T: Sync, F: Sync> Sync for ExtractIf<'a, T, F> {}
+
+impl<'a, T, F> Iterator for ExtractIf<'a, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while let Some(node) = self.next {
+            unsafe {
+                self.next = node.as_ref().next;
+                if (self.pred)(&mut (*node.as_ptr()).data) {
+                    return Some(self.list.take_node(node));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// A read-only cursor over a `MyLinkedList`.
+///
+/// The cursor always rests between two elements, or on the "ghost" position
+/// past the end of the list, where `current` returns `None`. Moving past the
+/// ghost wraps around to the other end.
+pub struct Cursor<'a, T: 'a> {
+    index: usize,
+    current: Option<NonNull<Node<T>>>,
+    list: &'a MyLinkedList<T>,
+}
+
+unsafe impl<'a, T: Sync> Send for Cursor<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for Cursor<'a, T> {}
+
+/// A cursor over a `MyLinkedList` that can edit the list in place.
+///
+/// Insertions and removals at the cursor are O(1); see `Cursor` for how the
+/// ghost position behaves.
+pub struct CursorMut<'a, T: 'a> {
+    index: usize,
+    current: Option<NonNull<Node<T>>>,
+    list: &'a mut MyLinkedList<T>,
+}
+
+unsafe impl<'a, T: Send> Send for CursorMut<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for CursorMut<'a, T> {}
+
+impl<'a, T> Clone for Cursor<'a, T> {
+    fn clone(&self) -> Self {
+        Cursor { index: self.index, current: self.current, list: self.list }
+    }
+}
+
+impl<'a, T> Cursor<'a, T> {
+    /// Index of the current element, or `None` on the ghost position.
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        match self.current.take() {
+            None => {
+                self.current = self.list.head;
+                self.index = 0;
+            }
+            Some(node) => unsafe {
+                self.current = node.as_ref().next;
+                self.index += 1;
+            },
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        match self.current.take() {
+            None => {
+                self.current = self.list.tail;
+                self.index = self.list.size.saturating_sub(1);
+            }
+            Some(node) => unsafe {
+                self.current = node.as_ref().prev;
+                self.index = self.index.checked_sub(1).unwrap_or(self.list.size);
+            },
+        }
+    }
+
+    pub fn current(&self) -> Option<&'a T> {
+        unsafe { self.current.map(|node| &(*node.as_ptr()).data) }
+    }
+
+    pub fn peek_next(&self) -> Option<&'a T> {
+        unsafe {
+            let next = match self.current {
+                None => self.list.head,
+                Some(node) => node.as_ref().next,
+            };
+            next.map(|node| &(*node.as_ptr()).data)
+        }
+    }
+
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        unsafe {
+            let prev = match self.current {
+                None => self.list.tail,
+                Some(node) => node.as_ref().prev,
+            };
+            prev.map(|node| &(*node.as_ptr()).data)
+        }
+    }
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    /// Index of the current element, or `None` on the ghost position.
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        match self.current.take() {
+            None => {
+                self.current = self.list.head;
+                self.index = 0;
+            }
+            Some(node) => unsafe {
+                self.current = node.as_ref().next;
+                self.index += 1;
+            },
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        match self.current.take() {
+            None => {
+                self.current = self.list.tail;
+                self.index = self.list.size.saturating_sub(1);
+            }
+            Some(node) => unsafe {
+                self.current = node.as_ref().prev;
+                self.index = self.index.checked_sub(1).unwrap_or(self.list.size);
+            },
+        }
+    }
+
+    pub fn current(&mut self) -> Option<&mut T> {
+        unsafe { self.current.map(|node| &mut (*node.as_ptr()).data) }
+    }
+
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        unsafe {
+            let next = match self.current {
+                None => self.list.head,
+                Some(node) => node.as_ref().next,
+            };
+            next.map(|node| &mut (*node.as_ptr()).data)
+        }
+    }
+
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        unsafe {
+            let prev = match self.current {
+                None => self.list.tail,
+                Some(node) => node.as_ref().prev,
+            };
+            prev.map(|node| &mut (*node.as_ptr()).data)
+        }
+    }
+
+    /// A read-only cursor at the same position, borrowing from this one.
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor { index: self.index, current: self.current, list: self.list }
+    }
+
+    /// Inserts `data` after the current element.
+    /// On the ghost position the element becomes the new front.
+    pub fn insert_after(&mut self, data: T) {
+        let new_node = NonNull::from(Box::leak(Box::new(Node::new(data))));
+        let next = match self.current {
+            None => self.list.head,
+            Some(node) => unsafe { node.as_ref().next },
+        };
+        self.list.splice_nodes(self.current, next, new_node, new_node, 1);
+        if self.current.is_none() {
+            self.index = self.list.size;
+        }
+    }
+
+    /// Inserts `data` before the current element.
+    /// On the ghost position the element becomes the new back.
+    pub fn insert_before(&mut self, data: T) {
+        let new_node = NonNull::from(Box::leak(Box::new(Node::new(data))));
+        let prev = match self.current {
+            None => self.list.tail,
+            Some(node) => unsafe { node.as_ref().prev },
+        };
+        self.list.splice_nodes(prev, self.current, new_node, new_node, 1);
+        self.index += 1;
+    }
+
+    /// Removes the current element and moves the cursor to the next one.
+    /// Returns `None` on the ghost position.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let unlinked = self.current?;
+        unsafe {
+            self.current = unlinked.as_ref().next;
+            self.list.unlink_node(unlinked);
+            self.list.release_slot(unlinked);
+            Some(Box::from_raw(unlinked.as_ptr()).into_value())
+        }
+    }
+
+    /// Splits the list after the current element, returning everything behind it.
+    /// On the ghost position the whole list is returned.
+    pub fn split_after(&mut self) -> MyLinkedList<T> {
+        let at = if self.current.is_none() {
+            self.index = 0;
+            0
+        } else {
+            self.index + 1
+        };
+        self.list.split_off_after_node(self.current, at)
+    }
+
+    /// Splits the list before the current element, returning everything in front of it.
+    /// On the ghost position the whole list is returned.
+    pub fn split_before(&mut self) -> MyLinkedList<T> {
+        let at = self.index;
+        self.index = 0;
+        self.list.split_off_before_node(self.current, at)
+    }
+}
+
 
 
 
 #[cfg(test)]
 mod test {
+    use super::MyLinkedList;
+    use crate::project_errors::CollectionError;
 
     #[test]
     fn this_is_a_test() {}
+
+    fn list_of(values: &[i32]) -> MyLinkedList<i32> {
+        let mut list = MyLinkedList::new();
+        for &x in values {
+            list.push_back(x);
+        }
+        list
+    }
+
+    #[test]
+    fn cursor_walks_and_wraps_through_ghost() {
+        let list = list_of(&[1, 2, 3]);
+        let mut cursor = list.cursor_front();
+        assert_eq!(cursor.current(), Some(&1));
+        assert_eq!(cursor.peek_prev(), None);
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&3)));
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (None, None));
+        assert_eq!(cursor.peek_next(), Some(&1));
+        assert_eq!(cursor.peek_prev(), Some(&3));
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&1));
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&3)));
+    }
+
+    #[test]
+    fn cursor_mut_inserts_and_removes_in_place() {
+        let mut list = list_of(&[1, 3, 5]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_before(0);
+        cursor.insert_after(2);
+        assert_eq!(cursor.index(), Some(1));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(3));
+        assert_eq!(cursor.current(), Some(&mut 5));
+        cursor.move_next();
+        cursor.insert_after(-1);
+        cursor.insert_before(6);
+        *cursor.peek_next().unwrap() -= 1;
+        assert_eq!(cursor.remove_current(), None);
+        assert_eq!(list.size(), 6);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![-2, 0, 1, 2, 5, 6]);
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![6, 5, 2, 1, 0, -2]);
+    }
+
+    #[test]
+    fn cursor_mut_splits() {
+        let mut list = list_of(&[1, 2, 3, 4, 5]);
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        let tail = cursor.split_after();
+        let head = cursor.split_before();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2]);
+        assert_eq!((head.size(), tail.size()), (1, 3));
+        assert_eq!(head.iter().copied().collect::<Vec<_>>(), vec![1]);
+        assert_eq!(tail.iter().rev().copied().collect::<Vec<_>>(), vec![5, 4, 3]);
+
+        let mut list = list_of(&[1, 2]);
+        let mut cursor = list.cursor_back_mut();
+        cursor.move_next();
+        let all = cursor.split_after();
+        assert!(list.is_empty());
+        assert_eq!(all.size(), 2);
+    }
+
+    #[test]
+    fn handles_remove_and_reorder() {
+        let mut list = MyLinkedList::new();
+        let a = list.push_back_handle('a');
+        let b = list.push_back_handle('b');
+        let c = list.push_front_handle('c');
+        list.push_back('d');
+
+        assert_eq!(list.get_by_handle(b), Some(&'b'));
+        *list.get_by_handle_mut(a).unwrap() = 'A';
+        assert!(list.move_to_back(c).is_ok());
+        assert!(list.move_to_front(b).is_ok());
+        assert_eq!(list.iter().copied().collect::<String>(), "bAdc");
+
+        assert_eq!(list.remove_by_handle(a), Some('A'));
+        assert_eq!(list.iter().rev().copied().collect::<String>(), "cdb");
+        assert_eq!(list.size(), 3);
+    }
+
+    #[test]
+    fn stale_handles_are_rejected() {
+        let mut list = MyLinkedList::new();
+        let a = list.push_back_handle(1);
+        let b = list.push_back_handle(2);
+        assert_eq!(list.remove_by_handle(a), Some(1));
+        assert_eq!(list.remove_by_handle(a), None);
+        assert!(list.move_to_front(a).is_err());
+
+        // the freed slot is reused with a new generation
+        let c = list.push_back_handle(3);
+        assert_eq!(list.get_by_handle(a), None);
+        assert_eq!(list.get_by_handle(c), Some(&3));
+
+        assert_eq!(list.pop_front(), Some(2));
+        assert!(!list.contains_handle(b));
+
+        let other = list_of(&[3]);
+        assert_eq!(other.get_by_handle(c), None);
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        let split = cursor.split_after();
+        assert_eq!(split.iter().copied().collect::<Vec<_>>(), vec![3]);
+        assert_eq!(split.get_by_handle(c), None);
+        assert_eq!(list.get_by_handle(c), None);
+    }
+
+    #[test]
+    fn moved_nodes_leave_their_handles_behind() {
+        let mut list = MyLinkedList::new();
+        let moved = list.push_back_handle(1);
+        list.push_back(2);
+        let mut other = MyLinkedList::new();
+        let kept = other.push_back_handle(10);
+        let next = other.push_back_handle(11);
+
+        // the moved node still remembers slot 0, which `other` uses for `kept`
+        other.prepend(&mut list);
+        assert_eq!(list.get_by_handle(moved), None);
+        assert_eq!(other.get_by_handle(moved), None);
+        assert_eq!(other.pop_front(), Some(1));
+        assert_eq!(other.get_by_handle(kept), Some(&10));
+
+        // emptied lists hand out handles again
+        let fresh = list.push_back_handle(5);
+        assert_eq!(list.get_by_handle(fresh), Some(&5));
+        list.append(&mut other);
+        assert_eq!(list.get_by_handle(next), None);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![5, 2, 10, 11]);
+        assert_eq!(list.remove_by_handle(fresh), Some(5));
+    }
+
+    #[test]
+    fn index_errors_carry_context() {
+        let mut list = list_of(&[1, 2, 3]);
+        assert_eq!(
+            list.insert(4, 0),
+            Err(CollectionError::IndexOutOfRange { operation: "insert", index: 4, len: 3 })
+        );
+        let err = list.remove(7).unwrap_err();
+        assert_eq!(err.to_string(), "remove: index 7 out of the range for length 3.");
+
+        let handle = list.push_back_handle(4);
+        list.remove_by_handle(handle);
+        assert_eq!(
+            list.move_to_back(handle),
+            Err(CollectionError::InvalidHandle { operation: "move_to_back" })
+        );
+    }
+
+    #[test]
+    fn std_conversions_and_iteration() {
+        let mut list: MyLinkedList<i32> = (1..=3).collect();
+        list.extend(vec![4, 5]);
+        list.extend(&[6]);
+        assert_eq!(list, MyLinkedList::from([1, 2, 3, 4, 5, 6]));
+        assert_eq!(list, MyLinkedList::from(vec![1, 2, 3, 4, 5, 6]));
+
+        for x in &mut list {
+            *x *= 2;
+        }
+        let mut sum = 0;
+        for x in &list {
+            sum += x;
+        }
+        assert_eq!(sum, 42);
+        assert_eq!(list.iter().len(), 6);
+        assert_eq!(list.into_iter().rev().collect::<Vec<_>>(), vec![12, 10, 8, 6, 4, 2]);
+    }
+
+    #[test]
+    fn clone_debug_compare_and_hash() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        fn hash_of<T: Hash>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let list = MyLinkedList::from(["a".to_string(), "b".to_string()]);
+        let copy = list.clone();
+        assert_eq!(list, copy);
+        assert_eq!(hash_of(&list), hash_of(&copy));
+        assert_eq!(format!("{:?}", copy), r#"["a", "b"]"#);
+
+        assert!(MyLinkedList::from([1, 2]) < MyLinkedList::from([1, 3]));
+        assert!(MyLinkedList::from([1, 2]) > MyLinkedList::from([1]));
+        assert_eq!(MyLinkedList::from([2]).cmp(&MyLinkedList::from([2])), std::cmp::Ordering::Equal);
+        assert_ne!(MyLinkedList::from([1, 2]), MyLinkedList::from([1]));
+        assert!(MyLinkedList::from([f64::NAN]).partial_cmp(&MyLinkedList::from([1.0])).is_none());
+    }
+
+    #[test]
+    fn append_prepend_and_splice_relink() {
+        let mut list = list_of(&[3, 4]);
+        let kept = list.push_back_handle(5);
+        let mut other = MyLinkedList::new();
+        let moved = other.push_back_handle(6);
+        list.append(&mut other);
+        assert!(other.is_empty());
+        list.prepend(&mut list_of(&[1, 2]));
+        list.append(&mut MyLinkedList::new());
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![6, 5, 4, 3, 2, 1]);
+        assert_eq!(list.get_by_handle(kept), Some(&5));
+        assert_eq!(list.get_by_handle(moved), None);
+
+        assert!(list.splice_at(0, &mut list_of(&[-1, 0])).is_ok());
+        assert!(list.splice_at(5, &mut list_of(&[30, 31])).is_ok());
+        assert!(list.splice_at(10, &mut list_of(&[7])).is_ok());
+        assert_eq!(
+            list.splice_at(12, &mut list_of(&[8])),
+            Err(CollectionError::IndexOutOfRange { operation: "splice_at", index: 12, len: 11 })
+        );
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![-1, 0, 1, 2, 3, 30, 31, 4, 5, 6, 7]);
+        assert_eq!(list.size(), 11);
+        assert_eq!(list.iter().rev().count(), 11);
+    }
+
+    #[test]
+    fn split_off_walks_from_either_end() {
+        let mut list = list_of(&[1, 2, 3, 4, 5, 6]);
+        let front = list.push_front_handle(0);
+        let back = list.push_back_handle(7);
+
+        let tail = list.split_off(6);
+        assert_eq!(tail.iter().copied().collect::<Vec<_>>(), vec![6, 7]);
+        assert_eq!(tail.get_by_handle(back), None);
+        let middle = list.split_off(2);
+        assert_eq!(middle.iter().rev().copied().collect::<Vec<_>>(), vec![5, 4, 3, 2]);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(list.get_by_handle(front), Some(&0));
+
+        assert!(list.split_off(2).is_empty());
+        let all = list.split_off(0);
+        assert_eq!((list.size(), all.size()), (0, 2));
+        assert_eq!(all.peek_back(), Some(&1));
+    }
+
+    #[test]
+    fn merge_sort_is_stable_and_keeps_handles() {
+        let mut list = MyLinkedList::new();
+        let handles: Vec<_> = [(3, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (3, 'e'), (0, 'f'), (2, 'g')]
+            .into_iter()
+            .map(|pair| list.push_back_handle(pair))
+            .collect();
+        list.sort_by_key(|&(key, _)| key);
+        let labels: String = list.iter().map(|&(_, label)| label).collect();
+        assert_eq!(labels, "fbdcgae");
+        assert_eq!(list.iter().rev().map(|&(_, label)| label).collect::<String>(), "eagcdbf");
+        assert_eq!(list.get_by_handle(handles[4]), Some(&(3, 'e')));
+        assert!(list.move_to_front(handles[4]).is_ok());
+        assert_eq!(list.peek_front(), Some(&(3, 'e')));
+
+        let mut numbers = list_of(&[5, -1, 4, 4, 9, 0, 2, 8, 1, 7, 3, 6]);
+        assert!(!numbers.is_sorted());
+        numbers.sort();
+        assert!(numbers.is_sorted());
+        assert_eq!(numbers.iter().copied().collect::<Vec<_>>(), vec![-1, 0, 1, 2, 3, 4, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(numbers.peek_back(), Some(&9));
+        numbers.sort_by(|a, b| b.cmp(a));
+        assert_eq!(numbers.iter().rev().copied().collect::<Vec<_>>(), vec![-1, 0, 1, 2, 3, 4, 4, 5, 6, 7, 8, 9]);
+
+        let mut empty: MyLinkedList<i32> = MyLinkedList::new();
+        empty.sort();
+        assert!(empty.is_empty() && empty.is_sorted());
+    }
+
+    #[test]
+    fn panicking_comparator_keeps_every_node() {
+        use std::panic::{self, AssertUnwindSafe};
+
+        for panic_at in 1..=8 {
+            let mut list = MyLinkedList::new();
+            let handles: Vec<_> = [1, 0, 3, 2, 9].into_iter().map(|x| list.push_back_handle(x)).collect();
+            let mut calls = 0;
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                list.sort_by(|a, b| {
+                    calls += 1;
+                    assert!(calls != panic_at, "comparator panicked");
+                    a.cmp(b)
+                })
+            }));
+            assert_eq!(result.is_err(), calls == panic_at);
+
+            let mut forward: Vec<i32> = list.iter().copied().collect();
+            let backward: Vec<i32> = list.iter().rev().copied().collect();
+            assert_eq!(forward.iter().rev().copied().collect::<Vec<_>>(), backward);
+            forward.sort();
+            assert_eq!(forward, vec![0, 1, 2, 3, 9]);
+            assert_eq!(list.size(), 5);
+            assert_eq!(list.get_by_handle(handles[4]), Some(&9));
+
+            assert!(list.pop_back().is_some() && list.pop_back().is_some());
+            assert!(list.pop_front().is_some() && list.pop_front().is_some());
+            assert_eq!(list.size(), 1);
+        }
+
+        let mut list = list_of(&[1, 4, 7]);
+        let mut other = list_of(&[2, 3]);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            list.sort_by(|_, _| panic!("comparator panicked"));
+        }));
+        assert!(result.is_err());
+        list.merge_sorted(&mut other);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 7]);
+    }
+
+    #[test]
+    fn merge_sorted_interleaves_two_lists() {
+        let mut list = list_of(&[1, 3, 5, 7]);
+        let mut other = list_of(&[0, 3, 4, 9, 10]);
+        list.merge_sorted(&mut other);
+        assert!(other.is_empty());
+        assert_eq!(list.size(), 9);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1, 3, 3, 4, 5, 7, 9, 10]);
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![10, 9, 7, 5, 4, 3, 3, 1, 0]);
+
+        let mut empty = MyLinkedList::new();
+        empty.merge_sorted(&mut list);
+        assert_eq!(empty.size(), 9);
+        empty.merge_sorted(&mut MyLinkedList::new());
+        assert_eq!(empty.peek_back(), Some(&10));
+    }
+
+    #[test]
+    fn retain_and_dedup_in_one_pass() {
+        let mut list = list_of(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        let odd = list.push_back_handle(9);
+        let even = list.push_back_handle(10);
+        list.retain(|x| x % 2 == 1);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 3, 5, 7, 9]);
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![9, 7, 5, 3, 1]);
+        assert_eq!(list.get_by_handle(odd), Some(&9));
+        assert_eq!(list.get_by_handle(even), None);
+
+        list.retain_mut(|x| {
+            *x *= 10;
+            *x > 10
+        });
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![30, 50, 70, 90]);
+        list.retain(|_| false);
+        assert!(list.is_empty());
+        assert_eq!(list.peek_back(), None);
+
+        let mut runs = list_of(&[1, 1, 2, 3, 3, 3, 1, 4, 4]);
+        runs.dedup();
+        assert_eq!(runs.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 1, 4]);
+        assert_eq!(runs.peek_back(), Some(&4));
+        runs.dedup_by_key(|x| *x / 2);
+        assert_eq!(runs.iter().copied().collect::<Vec<_>>(), vec![1, 2, 1, 4]);
+        runs.dedup_by(|a, b| a > b);
+        assert_eq!(runs.iter().rev().copied().collect::<Vec<_>>(), vec![1, 1]);
+        assert_eq!(runs.size(), 2);
+    }
+
+    #[test]
+    fn extract_if_is_lazy() {
+        let mut list = list_of(&[1, 2, 3, 4, 5, 6]);
+        // stopping after the first match leaves the rest untouched
+        assert_eq!(list.extract_if(|x| *x % 2 == 0).next(), Some(2));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 3, 4, 5, 6]);
+
+        let extracted: Vec<_> = list.extract_if(|x| *x > 3).collect();
+        assert_eq!(extracted, vec![4, 5, 6]);
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![3, 1]);
+        assert_eq!(list.size(), 2);
+    }
+
+    #[test]
+    fn reverse_swaps_every_link() {
+        let mut list = list_of(&[1, 2, 3, 4]);
+        let handle = list.push_back_handle(5);
+        list.reverse();
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![5, 4, 3, 2, 1]);
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(list.get_by_handle(handle), Some(&5));
+        list.push_back(0);
+        assert_eq!(list.pop_front(), Some(5));
+
+        let mut single = list_of(&[1]);
+        single.reverse();
+        assert_eq!(single.peek_back(), Some(&1));
+        let mut empty: MyLinkedList<i32> = MyLinkedList::new();
+        empty.reverse();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn rotate_relinks_the_seam() {
+        let mut list = list_of(&[1, 2, 3, 4, 5]);
+        list.rotate_left(2);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5, 1, 2]);
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![2, 1, 5, 4, 3]);
+        list.rotate_right(4);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![4, 5, 1, 2, 3]);
+        list.rotate_left(0);
+        list.rotate_right(5);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![4, 5, 1, 2, 3]);
+        assert_eq!((list.peek_front(), list.peek_back()), (Some(&4), Some(&3)));
+    }
+
+    #[test]
+    #[should_panic(expected = "rotate_left: index 3 out of the range for length 2.")]
+    fn rotate_past_the_end_panics() {
+        list_of(&[1, 2]).rotate_left(3);
+    }
+
+    #[test]
+    fn swap_relinks_nodes() {
+        let mut list = list_of(&[0, 1, 2, 3, 4, 5]);
+        let handle = list.push_front_handle(-1);
+        assert!(list.swap(0, 6).is_ok());
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![5, 0, 1, 2, 3, 4, -1]);
+        assert!(list.swap(3, 2).is_ok());
+        assert!(list.swap(4, 6).is_ok());
+        assert!(list.swap(1, 1).is_ok());
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![5, 0, 2, 1, -1, 4, 3]);
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![3, 4, -1, 1, 2, 0, 5]);
+        assert!(list.move_to_back(handle).is_ok());
+        assert_eq!(list.peek_back(), Some(&-1));
+        assert_eq!(
+            list.swap(2, 7),
+            Err(CollectionError::IndexOutOfRange { operation: "swap", index: 7, len: 7 })
+        );
+    }
+
+    #[test]
+    fn get_reaches_every_position_from_either_end() {
+        for len in 0..=6 {
+            let values: Vec<i32> = (0..len).collect();
+            let mut list = list_of(&values);
+            for idx in 0..len as usize {
+                assert_eq!(list.get(idx), Some(&(idx as i32)));
+                assert_eq!(list[idx], idx as i32);
+                *list.get_mut(idx).unwrap() += 10;
+                list[idx] += 10;
+            }
+            assert_eq!(list.get(len as usize), None);
+            assert_eq!(list.get_mut(len as usize), None);
+            assert_eq!(list.get(usize::MAX), None);
+            assert_eq!(list.iter().copied().collect::<Vec<_>>(), values.iter().map(|x| x + 20).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn insert_accepts_every_position_up_to_len() {
+        for len in 0..=5 {
+            for idx in 0..=len {
+                let mut expected: Vec<i32> = (0..len as i32).collect();
+                let mut list = list_of(&expected);
+                assert_eq!(list.insert(idx, -1), Ok(()));
+                expected.insert(idx, -1);
+                assert_eq!(list.iter().copied().collect::<Vec<_>>(), expected);
+                assert_eq!(list.iter().rev().count(), len + 1);
+                assert_eq!(list.size(), len + 1);
+            }
+            let mut list = list_of(&(0..len as i32).collect::<Vec<_>>());
+            assert_eq!(
+                list.insert(len + 1, -1),
+                Err(CollectionError::IndexOutOfRange { operation: "insert", index: len + 1, len })
+            );
+        }
+    }
+
+    #[test]
+    fn remove_takes_every_position() {
+        for len in 0..=5 {
+            for idx in 0..len {
+                let mut expected: Vec<i32> = (0..len as i32).collect();
+                let mut list = list_of(&expected);
+                assert_eq!(list.remove(idx), Ok(idx as i32));
+                expected.remove(idx);
+                assert_eq!(list.iter().copied().collect::<Vec<_>>(), expected);
+                assert_eq!(list.iter().rev().count(), len - 1);
+                assert_eq!((list.peek_front(), list.peek_back()), (expected.first(), expected.last()));
+            }
+            let mut list = list_of(&(0..len as i32).collect::<Vec<_>>());
+            assert_eq!(
+                list.remove(len),
+                Err(CollectionError::IndexOutOfRange { operation: "remove", index: len, len })
+            );
+        }
+    }
+
+    #[test]
+    fn removing_by_index_invalidates_the_handle() {
+        let mut list = list_of(&[1, 2]);
+        let handle = list.push_back_handle(3);
+        assert_eq!(list.remove(2), Ok(3));
+        assert_eq!(list.get_by_handle(handle), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "index: index 3 out of the range for length 3.")]
+    fn index_past_the_end_panics() {
+        let list = list_of(&[1, 2, 3]);
+        let _ = list[3];
+    }
 }
@@ -1,5 +1,6 @@
-use crate::project_errors::{EmptyList, NotValidIndexError };
-use std::error::Error;
+use crate::project_errors::CollectionError;
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
 use std::ptr::NonNull;
@@ -68,13 +69,91 @@ impl<T> DerefMut for Node<T> {
     }
 }
 
+// A merge in progress. Dropping it, normally or during a panic in the
+// comparator, joins the merged prefix and both remainders into `out`.
+struct MergeChains<'a, T> {
+    head: Option<NonNull<Node<T>>>,
+    tail: Option<NonNull<Node<T>>>,
+    left: Option<NonNull<Node<T>>>,
+    right: Option<NonNull<Node<T>>>,
+    out: &'a mut Option<NonNull<Node<T>>>,
+}
+
+impl<T> Drop for MergeChains<'_, T> {
+    fn drop(&mut self) {
+        let rest = concat_chains(self.left.take(), self.right.take());
+        match self.tail {
+            Some(mut tail) => unsafe { tail.as_mut().next = rest },
+            None => self.head = rest,
+        }
+        *self.out = self.head;
+    }
+}
+
+// Holds the data nodes of a list while they are relinked outside of it, and
+// hooks all of them back between the sentinels when dropped, also when the
+// comparator panics.
+struct RelinkGuard<'a, T> {
+    list: &'a mut MyLinkedList2<T>,
+    size: usize,
+    // runs[i] is empty or a sorted run of 2^i nodes; higher runs hold earlier nodes
+    runs: [Option<NonNull<Node<T>>>; usize::BITS as usize],
+    carry: Option<NonNull<Node<T>>>,
+    // not yet looked at
+    rest: Option<NonNull<Node<T>>>,
+}
+
+impl<'a, T> RelinkGuard<'a, T> {
+    fn new(list: &'a mut MyLinkedList2<T>) -> Self {
+        let (rest, size) = list.detach_chain();
+        Self {
+            list,
+            size,
+            runs: [None; usize::BITS as usize],
+            carry: None,
+            rest,
+        }
+    }
+}
+
+impl<T> Drop for RelinkGuard<'_, T> {
+    fn drop(&mut self) {
+        let mut chain = concat_chains(self.carry.take(), self.rest.take());
+        for run in self.runs.iter_mut() {
+            chain = concat_chains(run.take(), chain);
+        }
+        self.list.attach_chain(chain, self.size);
+    }
+}
+
+// Joins two chains linked through `next`, walking the first one.
+fn concat_chains<T>(
+    first: Option<NonNull<Node<T>>>,
+    second: Option<NonNull<Node<T>>>,
+) -> Option<NonNull<Node<T>>> {
+    let Some(mut last) = first else {
+        return second;
+    };
+    unsafe {
+        while let Some(next) = last.as_ref().next {
+            last = next;
+        }
+        last.as_mut().next = second;
+    }
+    first
+}
+
 pub struct MyLinkedList2<T> {
     size: usize,
     head: Option<NonNull<Node<T>>>,
     tail: Option<NonNull<Node<T>>>,
-    _marker: PhantomData<T>,
+    _marker: PhantomData<Box<Node<T>>>,
 }
 
+// The list owns its nodes like a `Box` would, so it is as thread-safe as `T`.
+unsafe impl<T: Send> Send for MyLinkedList2<T> {}
+unsafe impl<T: Sync> Sync for MyLinkedList2<T> {}
+
 impl<T> MyLinkedList2<T> {
     pub fn new() -> Self {
         let head_sentinel = NonNull::new(Box::into_raw(Box::new(Node::new())));
@@ -109,23 +188,23 @@ impl<T> MyLinkedList2<T> {
         unsafe { self.tail.unwrap().as_ref().prev }
     }
 
-    fn check_available_index(&self, idx: usize) -> Result<(), Box<dyn Error>> {
+    fn check_available_index(&self, idx: usize, operation: &'static str) -> Result<(), CollectionError> {
         if !_is_available_index(idx, self.size) {
-            return Err(Box::new(NotValidIndexError {}));
+            return Err(CollectionError::IndexOutOfRange { operation, index: idx, len: self.size });
         }
         Ok(())
     }
 
-    fn check_element_index(&self, idx: usize) -> Result<(), Box<dyn Error>> {
+    fn check_element_index(&self, idx: usize, operation: &'static str) -> Result<(), CollectionError> {
         if !_is_element_index(idx, self.size) {
-            return Err(Box::new(NotValidIndexError {}));
+            return Err(CollectionError::IndexOutOfRange { operation, index: idx, len: self.size });
         }
         Ok(())
     }
 
-    pub fn pop_front(&mut self) -> Result<T, Box<dyn Error>> {
+    pub fn pop_front(&mut self) -> Result<T, CollectionError> {
         if self.is_empty() {
-            return Err(Box::new(EmptyList {}));
+            return Err(CollectionError::Empty { operation: "pop_front" });
         }
 
         unsafe {
@@ -143,9 +222,9 @@ impl<T> MyLinkedList2<T> {
         }
     }
 
-    pub fn pop_back(&mut self) -> Result<T, Box<dyn Error>> {
+    pub fn pop_back(&mut self) -> Result<T, CollectionError> {
         if self.is_empty() {
-            return Err(Box::new(EmptyList {}));
+            return Err(CollectionError::Empty { operation: "pop_back" });
         }
 
         unsafe {
@@ -188,8 +267,8 @@ impl<T> MyLinkedList2<T> {
 
         unsafe {
             let new_node_ptr = NonNull::new(Box::into_raw(new_node));
-            back.unwrap().as_mut().prev = new_node_ptr;
-            self.tail.unwrap().as_mut().next = new_node_ptr;
+            back.unwrap().as_mut().next = new_node_ptr;
+            self.tail.unwrap().as_mut().prev = new_node_ptr;
 
             self.size += 1;
         }
@@ -235,9 +314,8 @@ impl<T> MyLinkedList2<T> {
         }
     }
 
+    // The node at `index`; callers check the index and report their own name.
     fn _get_index_cur(&self, index: usize) -> Option<NonNull<Node<T>>> {
-        let _ = self.check_element_index(index);
-
         unsafe {
             let mut cur = self._get_front();
             for _ in 0..index {
@@ -247,23 +325,23 @@ impl<T> MyLinkedList2<T> {
         }
     }
 
-    pub fn get(&self, index: usize) -> Result<Option<&T>, Box<dyn Error>> {
+    pub fn get(&self, index: usize) -> Result<Option<&T>, CollectionError> {
         if self.is_empty() {
-            return Err(Box::new(EmptyList {}));
+            return Err(CollectionError::Empty { operation: "get" });
         }
 
-        self.check_element_index(index)?;
+        self.check_element_index(index, "get")?;
 
         let data_option = unsafe { self._get_index_cur(index).unwrap().as_ref().get_ref() };
         Ok(data_option)
     }
 
-    pub fn get_mut(&mut self, index: usize) -> Result<Option<&mut T>, Box<dyn Error>> {
+    pub fn get_mut(&mut self, index: usize) -> Result<Option<&mut T>, CollectionError> {
         if self.is_empty() {
-            return Err(Box::new(EmptyList {}));
+            return Err(CollectionError::Empty { operation: "get_mut" });
         }
 
-        self.check_element_index(index)?;
+        self.check_element_index(index, "get_mut")?;
 
         let data_option = unsafe { self._get_index_cur(index).unwrap().as_mut().get_mut() };
 
@@ -271,10 +349,10 @@ impl<T> MyLinkedList2<T> {
 
     }
 
-    pub fn set(&mut self, index: usize, data: T) -> Result<(), Box<dyn Error>> {
-        if self.is_empty() { return Err(Box::new(EmptyList {})) }
+    pub fn set(&mut self, index: usize, data: T) -> Result<(), CollectionError> {
+        if self.is_empty() { return Err(CollectionError::Empty { operation: "set" }) }
 
-        self.check_element_index(index)?;
+        self.check_element_index(index, "set")?;
 
         let cur = self._get_index_cur(index);
         unsafe {
@@ -283,9 +361,9 @@ impl<T> MyLinkedList2<T> {
         }
     }
 
-    pub fn remove(&mut self, index: usize) -> Result<Option<T>, Box<dyn Error>> {
-        if self.is_empty() { return Err(Box::new(EmptyList {})); }
-        self.check_element_index(index)?;
+    pub fn remove(&mut self, index: usize) -> Result<Option<T>, CollectionError> {
+        if self.is_empty() { return Err(CollectionError::Empty { operation: "remove" }); }
+        self.check_element_index(index, "remove")?;
 
         let cur = self._get_index_cur(index);
         unsafe {
@@ -301,11 +379,244 @@ impl<T> MyLinkedList2<T> {
         }
     }
 
-    fn into_iter(self) -> IntoIter<T> {
-        IntoIter { list: self }
+    /// Inserts `data` so that it ends up at `index`; `index == size` appends.
+    pub fn insert(&mut self, index: usize, data: T) -> Result<(), CollectionError> {
+        self.check_available_index(index, "insert")?;
+
+        // the node that will follow the new one; the tail sentinel when appending
+        let next = if index == self.size {
+            self.tail
+        } else {
+            self._get_index_cur(index)
+        };
+
+        unsafe {
+            let prev = next.unwrap().as_ref().prev;
+            let mut new_node = Box::new(Node::new_with(data));
+            new_node.prev = prev;
+            new_node.next = next;
+
+            let new_node_ptr = NonNull::new(Box::into_raw(new_node));
+            prev.unwrap().as_mut().next = new_node_ptr;
+            next.unwrap().as_mut().prev = new_node_ptr;
+        }
+
+        self.size += 1;
+        Ok(())
+    }
+
+    // Unhooks the data nodes from the sentinels as a chain linked through
+    // `next`, leaving the list empty. Returns the chain and its length.
+    fn detach_chain(&mut self) -> (Option<NonNull<Node<T>>>, usize) {
+        if self.is_empty() {
+            return (None, 0);
+        }
+        unsafe {
+            let front = self._get_front();
+            self._get_back().unwrap().as_mut().next = None;
+            self.head.unwrap().as_mut().next = self.tail;
+            self.tail.unwrap().as_mut().prev = self.head;
+            (front, mem::take(&mut self.size))
+        }
+    }
+
+    // Hooks a chain of `len` nodes linked through `next` in between the
+    // sentinels of the empty list, fixing up `prev` links.
+    fn attach_chain(&mut self, chain: Option<NonNull<Node<T>>>, len: usize) {
+        let mut prev = self.head;
+        let mut cur = chain;
+        unsafe {
+            while let Some(mut node) = cur {
+                prev.unwrap().as_mut().next = Some(node);
+                node.as_mut().prev = prev;
+                prev = Some(node);
+                cur = node.as_ref().next;
+            }
+            prev.unwrap().as_mut().next = self.tail;
+            self.tail.unwrap().as_mut().prev = prev;
+        }
+        self.size = len;
+    }
+
+    // Merges two sorted chains linked through `next` into `out`, preferring
+    // `left` on ties.
+    fn merge_chains<F>(
+        left: Option<NonNull<Node<T>>>,
+        right: Option<NonNull<Node<T>>>,
+        compare: &mut F,
+        out: &mut Option<NonNull<Node<T>>>,
+    ) where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut merge = MergeChains { head: None, tail: None, left, right, out };
+        unsafe {
+            while let (Some(l), Some(r)) = (merge.left, merge.right) {
+                let node = if compare(&**l.as_ref(), &**r.as_ref()) == Ordering::Greater {
+                    merge.right = r.as_ref().next;
+                    r
+                } else {
+                    merge.left = l.as_ref().next;
+                    l
+                };
+                match merge.tail {
+                    Some(mut tail) => tail.as_mut().next = Some(node),
+                    None => merge.head = Some(node),
+                }
+                merge.tail = Some(node);
+            }
+        }
+    }
+
+    /// Sorts the list with a stable bottom-up merge sort that relinks nodes
+    /// instead of moving elements.
+    ///
+    /// If `compare` panics, the list keeps all of its elements, in an
+    /// unspecified order.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut sort = RelinkGuard::new(self);
+        while let Some(mut node) = sort.rest {
+            sort.rest = unsafe { node.as_mut().next.take() };
+            sort.carry = Some(node);
+            let mut i = 0;
+            while let Some(run) = sort.runs[i].take() {
+                let carry = sort.carry.take();
+                Self::merge_chains(Some(run), carry, &mut compare, &mut sort.carry);
+                i += 1;
+            }
+            sort.runs[i] = sort.carry.take();
+        }
+
+        for i in 0..sort.runs.len() {
+            if let Some(run) = sort.runs[i].take() {
+                let later = sort.carry.take();
+                Self::merge_chains(Some(run), later, &mut compare, &mut sort.carry);
+            }
+        }
+    }
+
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    pub fn sort_by_key<K, F>(&mut self, mut f: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
+
+    pub fn is_sorted(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.iter().zip(self.iter().skip(1)).all(|(a, b)| a <= b)
+    }
+
+    /// Merges the already sorted `other` into this sorted list in a single pass,
+    /// leaving `other` empty. Equal elements from this list come first.
+    pub fn merge_sorted(&mut self, other: &mut Self)
+    where
+        T: Ord,
+    {
+        let (other_chain, other_size) = other.detach_chain();
+        let mut merge = RelinkGuard::new(self);
+        merge.size += other_size;
+        let mine = merge.rest.take();
+        Self::merge_chains(mine, other_chain, &mut T::cmp, &mut merge.carry);
+    }
+
+    // Unlinks the data node `node` from its neighbours and returns its element.
+    fn unlink_node(&mut self, node: NonNull<Node<T>>) -> T {
+        unsafe {
+            let node_box = Box::from_raw(node.as_ptr());
+            node_box.prev.unwrap().as_mut().next = node_box.next;
+            node_box.next.unwrap().as_mut().prev = node_box.prev;
+            self.size -= 1;
+            node_box.data.expect("Data unwrapped with None")
+        }
+    }
+
+    /// Keeps only the elements for which `keep` returns `true`, in one pass.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_mut(|elem| keep(elem));
+    }
+
+    pub fn retain_mut<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let mut cur = self._get_front();
+        while cur != self.tail {
+            let mut node = cur.unwrap();
+            unsafe {
+                cur = node.as_ref().next;
+                if !keep(&mut **node.as_mut()) {
+                    self.unlink_node(node);
+                }
+            }
+        }
+    }
+
+    /// Removes consecutive elements for which `same_bucket(current, previous)`
+    /// returns `true`, keeping the first of each run.
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        if self.is_empty() {
+            return;
+        }
+        let mut kept = self._get_front().unwrap();
+        let mut cur = unsafe { kept.as_ref().next };
+        while cur != self.tail {
+            let mut node = cur.unwrap();
+            unsafe {
+                cur = node.as_ref().next;
+                if same_bucket(&mut **node.as_mut(), &mut **kept.as_mut()) {
+                    self.unlink_node(node);
+                } else {
+                    kept = node;
+                }
+            }
+        }
+    }
+
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: PartialEq,
+        F: FnMut(&mut T) -> K,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    /// Returns an iterator that removes and yields the elements for which
+    /// `pred` returns `true`. Elements not yet visited when the iterator is
+    /// dropped stay in the list.
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        ExtractIf { next: self._get_front(), list: self, pred }
     }
 
-    fn iter(&self) -> Iter<'_, T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         Iter {
             front: self._get_front(),
             back: self._get_back(),
@@ -314,7 +625,7 @@ impl<T> MyLinkedList2<T> {
         }
     }
 
-    fn iter_mut(&mut self) -> IterMut<'_, T> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
         IterMut {
             front: self._get_front(),
             back: self._get_back(),
@@ -324,25 +635,137 @@ impl<T> MyLinkedList2<T> {
     }
 }
 
+impl<T> Default for MyLinkedList2<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FromIterator<T> for MyLinkedList2<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for MyLinkedList2<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for data in iter {
+            self.push_back(data);
+        }
+    }
+}
+
+impl<T> IntoIterator for MyLinkedList2<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a MyLinkedList2<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut MyLinkedList2<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+impl<T: Clone> Clone for MyLinkedList2<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: Debug> Debug for MyLinkedList2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for MyLinkedList2<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size && self.iter().eq(other)
+    }
+}
+
+impl<T: Eq> Eq for MyLinkedList2<T> {}
+
+impl<T> MyLinkedList2<T> {
+    // Only for `Drop`: the list must be empty and is unusable afterwards.
+    fn free_sentinels(&mut self) {
+        unsafe {
+            drop(Box::from_raw(self.head.take().unwrap().as_ptr()));
+            drop(Box::from_raw(self.tail.take().unwrap().as_ptr()));
+        }
+    }
+}
+
 impl<T> Drop for MyLinkedList2<T> {
     fn drop(&mut self) {
         struct DropGuard<'a, T>(&'a mut MyLinkedList2<T>);
 
         impl<'a, T> Drop for DropGuard<'a, T> {
             fn drop(&mut self) {
-                while self.0.pop_front().ok().is_some() {}
+                // keep dropping after a panic, then free the sentinels
+                while self.0.pop_front().is_ok() {}
+                self.0.free_sentinels();
             }
         }
 
-        while let Some(node) = self.pop_front().ok() {
+        while let Ok(node) = self.pop_front() {
             let guard = DropGuard(self);
             drop(node);
             mem::forget(guard);
         }
+        self.free_sentinels();
     }
 }
 
-struct IntoIter<T> {
+/// The iterator returned by `MyLinkedList2::extract_if`.
+pub struct ExtractIf<'a, T, F> {
+    list: &'a mut MyLinkedList2<T>,
+    next: Option<NonNull<Node<T>>>,
+    pred: F,
+}
+
+unsafe impl<'a, T: Send, F: Send> Send for ExtractIf<'a, T, F> {}
+unsafe impl<'a, T: Sync, F: Sync> Sync for ExtractIf<'a, T, F> {}
+
+impl<'a, T, F> Iterator for ExtractIf<'a, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while self.next != self.list.tail {
+            let mut node = self.next.unwrap();
+            unsafe {
+                self.next = node.as_ref().next;
+                if (self.pred)(&mut **node.as_mut()) {
+                    return Some(self.list.unlink_node(node));
+                }
+            }
+        }
+        None
+    }
+}
+
+pub struct IntoIter<T> {
     list: MyLinkedList2<T>,
 }
 
@@ -370,25 +793,28 @@ impl<T> Drop for IntoIter<T> {
     }
 }
 
-struct Iter<'a, T> {
+pub struct Iter<'a, T> {
     size: usize,
     front: Option<NonNull<Node<T>>>,
     back: Option<NonNull<Node<T>>>,
     _marker: PhantomData<&'a T>,
 }
 
+unsafe impl<'a, T: Sync> Send for Iter<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for Iter<'a, T> {}
+
 impl<'a, T> Iterator for Iter<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.front == self.back {
+        if self.size == 0 {
             return None;
         }
         unsafe {
-            let data_ref = &*self.front.unwrap().as_ref();
-            self.front = self.front.unwrap().as_ref().next;
+            let node = &*self.front?.as_ptr();
+            self.front = node.next;
             self.size -= 1;
-            Some(data_ref)
+            node.get_ref()
         }
     }
 
@@ -399,37 +825,40 @@ impl<'a, T> Iterator for Iter<'a, T> {
 
 impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
     fn next_back(&mut self) -> Option<Self::Item> {
-        if self.back == self.front {
+        if self.size == 0 {
             return None;
         }
         unsafe {
-            let data_ref = &*self.back.unwrap().as_ref();
-            self.back = self.back.unwrap().as_ref().prev;
+            let node = &*self.back?.as_ptr();
+            self.back = node.prev;
             self.size -= 1;
-            Some(data_ref)
+            node.get_ref()
         }
     }
 }
 
-struct IterMut<'a, T> {
+pub struct IterMut<'a, T> {
     size: usize,
     front: Option<NonNull<Node<T>>>,
     back: Option<NonNull<Node<T>>>,
     _marker: PhantomData<&'a mut T>,
 }
 
+unsafe impl<'a, T: Send> Send for IterMut<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for IterMut<'a, T> {}
+
 impl<'a, T> Iterator for IterMut<'a, T> {
     type Item = &'a mut T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.front == self.back {
+        if self.size == 0 {
             return None;
         }
         unsafe {
-            let data_ref = &mut *self.front.unwrap().as_mut();
-            self.front = self.front.unwrap().as_ref().next;
+            let node = &mut *self.front?.as_ptr();
+            self.front = node.next;
             self.size -= 1;
-            Some(data_ref)
+            node.get_mut()
         }
     }
 
@@ -440,14 +869,14 @@ impl<'a, T> Iterator for IterMut<'a, T> {
 
 impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
     fn next_back(&mut self) -> Option<Self::Item> {
-        if self.back == self.front {
+        if self.size == 0 {
             return None;
         }
         unsafe {
-            let data_ref = &mut *self.back.unwrap().as_mut();
-            self.back = self.back.unwrap().as_ref().prev;
+            let node = &mut *self.back?.as_ptr();
+            self.back = node.prev;
             self.size -= 1;
-            Some(data_ref)
+            node.get_mut()
         }
     }
 }
@@ -460,16 +889,23 @@ fn _is_element_index(idx: usize, size: usize) -> bool {
     idx < size
 }
 
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
 #[cfg(test)]
 mod test {
     use super::MyLinkedList2;
+    use crate::project_errors::CollectionError;
 
     #[test]
     fn test_compile() {}
 
     #[test]
     fn initialize_list() {
-        let my_list: MyLinkedList2<i32> = MyLinkedList2::new();
+        let _my_list: MyLinkedList2<i32> = MyLinkedList2::new();
     }
 
     #[test]
@@ -482,4 +918,200 @@ mod test {
             println!("{:?}", i)
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn errors_carry_context() {
+        let mut list: MyLinkedList2<i32> = MyLinkedList2::new();
+        assert_eq!(list.pop_back(), Err(CollectionError::Empty { operation: "pop_back" }));
+        list.push_front(1);
+        assert_eq!(
+            list.set(1, 5),
+            Err(CollectionError::IndexOutOfRange { operation: "set", index: 1, len: 1 })
+        );
+        assert_eq!(list.get(0), Ok(Some(&1)));
+    }
+
+    #[test]
+    fn iterators_visit_every_element() {
+        let mut list: MyLinkedList2<i32> = (1..=4).collect();
+        list.push_back(5);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![5, 4, 3, 2, 1]);
+
+        for x in &mut list {
+            *x *= 10;
+        }
+        let mut iter = list.iter_mut();
+        assert_eq!(iter.next(), Some(&mut 10));
+        assert_eq!(iter.next_back(), Some(&mut 50));
+        assert_eq!(iter.count(), 3);
+
+        let single: MyLinkedList2<i32> = std::iter::once(7).collect();
+        assert_eq!((&single).into_iter().next(), Some(&7));
+        assert_eq!(list.into_iter().rev().collect::<Vec<_>>(), vec![50, 40, 30, 20, 10]);
+    }
+
+    // `push_back` used to link the old back node through `prev` and never
+    // updated the tail sentinel, so backward walks skipped new elements.
+    #[test]
+    fn push_back_links_both_directions() {
+        let mut list = MyLinkedList2::new();
+        for i in 1..=3 {
+            list.push_back(i);
+        }
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![3, 2, 1]);
+        assert_eq!(list.pop_back(), Ok(3));
+        assert_eq!(list.pop_front(), Ok(1));
+        list.push_back(4);
+        assert_eq!(list.peek_back(), Some(&4));
+        assert_eq!(list.pop_back(), Ok(4));
+        assert_eq!(list.pop_back(), Ok(2));
+        assert_eq!(list.get_size(), 0);
+    }
+
+    // The iterators used to stop once `front == back`, one element early.
+    #[test]
+    fn iterators_yield_the_last_element() {
+        let list: MyLinkedList2<i32> = std::iter::once(1).collect();
+        assert_eq!(list.iter().next(), Some(&1));
+        assert_eq!(list.iter().next_back(), Some(&1));
+
+        let mut list: MyLinkedList2<i32> = (1..=2).collect();
+        let mut iter = list.iter_mut();
+        assert_eq!(iter.next(), Some(&mut 1));
+        assert_eq!(iter.next_back(), Some(&mut 2));
+        assert_eq!((iter.next(), iter.next_back()), (None, None));
+        assert_eq!(list.iter().rev().count(), 2);
+    }
+
+    #[test]
+    fn insert_at_every_position() {
+        let mut list = MyLinkedList2::new();
+        assert!(list.insert(0, 'b').is_ok());
+        assert!(list.insert(0, 'a').is_ok());
+        assert!(list.insert(2, 'd').is_ok());
+        assert!(list.insert(2, 'c').is_ok());
+        assert_eq!(
+            list.insert(5, 'x'),
+            Err(CollectionError::IndexOutOfRange { operation: "insert", index: 5, len: 4 })
+        );
+        assert_eq!(list.iter().collect::<String>(), "abcd");
+        assert_eq!(list.peek_back(), Some(&'d'));
+    }
+
+    #[test]
+    fn clone_debug_and_eq() {
+        let mut list = MyLinkedList2::new();
+        list.extend(vec!["x".to_string(), "y".to_string()]);
+        let copy = list.clone();
+        assert_eq!(list, copy);
+        assert_eq!(format!("{:?}", copy), r#"["x", "y"]"#);
+        list.pop_back().unwrap();
+        assert_ne!(list, copy);
+    }
+
+    #[test]
+    fn merge_sort_is_stable() {
+        let mut list: MyLinkedList2<(i32, char)> =
+            [(3, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (3, 'e'), (0, 'f'), (2, 'g')].into_iter().collect();
+        list.sort_by_key(|&(key, _)| key);
+        assert_eq!(list.iter().map(|&(_, label)| label).collect::<String>(), "fbdcgae");
+        assert_eq!(list.iter().rev().map(|&(_, label)| label).collect::<String>(), "eagcdbf");
+
+        let mut numbers: MyLinkedList2<i32> = vec![5, -1, 4, 4, 9, 0, 2, 8, 1, 7, 3, 6].into_iter().collect();
+        assert!(!numbers.is_sorted());
+        numbers.sort();
+        assert!(numbers.is_sorted());
+        assert_eq!(numbers.iter().copied().collect::<Vec<_>>(), vec![-1, 0, 1, 2, 3, 4, 4, 5, 6, 7, 8, 9]);
+        numbers.push_back(10);
+        numbers.push_front(-2);
+        assert_eq!((numbers.peek(), numbers.peek_back()), (Some(&-2), Some(&10)));
+
+        let mut empty: MyLinkedList2<i32> = MyLinkedList2::new();
+        empty.sort();
+        assert!(empty.is_empty() && empty.is_sorted());
+    }
+
+    #[test]
+    fn panicking_comparator_keeps_every_node() {
+        use std::panic::{self, AssertUnwindSafe};
+
+        let mut list: MyLinkedList2<i32> = vec![3, 2, 1].into_iter().collect();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| list.sort_by(|_, _| panic!("comparator panicked"))));
+        assert!(result.is_err());
+        assert_eq!(list.get_size(), 3);
+        assert_eq!(list.iter().rev().count(), 3);
+
+        for panic_at in 1..=8 {
+            let mut list: MyLinkedList2<i32> = vec![1, 0, 3, 2, 9].into_iter().collect();
+            let mut calls = 0;
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                list.sort_by(|a, b| {
+                    calls += 1;
+                    assert!(calls != panic_at, "comparator panicked");
+                    a.cmp(b)
+                })
+            }));
+            assert_eq!(result.is_err(), calls == panic_at);
+
+            let mut forward: Vec<i32> = list.iter().copied().collect();
+            let backward: Vec<i32> = list.iter().rev().copied().collect();
+            assert_eq!(forward.iter().rev().copied().collect::<Vec<_>>(), backward);
+            forward.sort();
+            assert_eq!(forward, vec![0, 1, 2, 3, 9]);
+            assert_eq!(list.get_size(), 5);
+            assert!(list.pop_front().is_ok() && list.pop_back().is_ok());
+            assert_eq!(list.get_size(), 3);
+        }
+    }
+
+    #[test]
+    fn merge_sorted_interleaves_two_lists() {
+        let mut list: MyLinkedList2<i32> = vec![1, 3, 5, 7].into_iter().collect();
+        let mut other: MyLinkedList2<i32> = vec![0, 3, 4, 9, 10].into_iter().collect();
+        list.merge_sorted(&mut other);
+        assert!(other.is_empty());
+        other.push_back(11);
+        assert_eq!(other.peek(), Some(&11));
+        assert_eq!(list.get_size(), 9);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1, 3, 3, 4, 5, 7, 9, 10]);
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![10, 9, 7, 5, 4, 3, 3, 1, 0]);
+
+        let mut empty = MyLinkedList2::new();
+        empty.merge_sorted(&mut list);
+        assert_eq!(empty.get_size(), 9);
+        assert_eq!(empty.pop_back(), Ok(10));
+    }
+
+    #[test]
+    fn retain_dedup_and_extract_if() {
+        let mut list: MyLinkedList2<i32> = (1..=10).collect();
+        list.retain(|x| x % 2 == 1);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 3, 5, 7, 9]);
+        list.retain_mut(|x| {
+            *x *= 10;
+            *x > 10
+        });
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![90, 70, 50, 30]);
+        list.retain(|_| false);
+        assert!(list.is_empty());
+        list.push_back(1);
+        assert_eq!(list.peek(), Some(&1));
+
+        let mut runs: MyLinkedList2<i32> = vec![1, 1, 2, 3, 3, 3, 1, 4, 4].into_iter().collect();
+        runs.dedup();
+        assert_eq!(runs.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 1, 4]);
+        runs.dedup_by_key(|x| *x / 2);
+        assert_eq!(runs.iter().copied().collect::<Vec<_>>(), vec![1, 2, 1, 4]);
+        runs.dedup_by(|a, b| a > b);
+        assert_eq!(runs.iter().rev().copied().collect::<Vec<_>>(), vec![1, 1]);
+
+        let mut list: MyLinkedList2<i32> = (1..=6).collect();
+        // stopping after the first match leaves the rest untouched
+        assert_eq!(list.extract_if(|x| *x % 2 == 0).next(), Some(2));
+        assert_eq!(list.extract_if(|x| *x > 3).collect::<Vec<_>>(), vec![4, 5, 6]);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(list.get_size(), 2);
+    }
+}
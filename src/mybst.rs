@@ -1,67 +1,190 @@
+use std::borrow::Borrow;
 use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::mem;
+use std::ops::{Bound, RangeBounds};
+use std::ptr::NonNull;
 #[allow(unused_imports)]
 use std::error::Error;
 
-struct BSTNode<T>
+struct BSTNode<K, V>
 where
-    T: Ord,
+    K: Ord,
 {
-    data: T,
-    left: Option<Box<BSTNode<T>>>,
-    right: Option<Box<BSTNode<T>>>,
+    key: K,
+    value: V,
+    left: Option<Box<BSTNode<K, V>>>,
+    right: Option<Box<BSTNode<K, V>>>,
+    height: usize,
+    // number of nodes in the subtree rooted here
+    size: usize,
 }
 
-impl<T> BSTNode<T>
+impl<K, V> BSTNode<K, V>
 where
-    T: Ord,
+    K: Ord,
 {
-    fn new(data: T) -> Self {
+    fn new(key: K, value: V) -> Self {
         Self {
-            data,
+            key,
+            value,
             left: None,
             right: None,
+            height: 1,
+            size: 1,
         }
     }
 
-    fn get_ref(&self) -> &T {
-        &self.data
+    fn take(self) -> (K, V) {
+        (self.key, self.value)
+    }
+
+    fn left_height(&self) -> usize {
+        self.left.as_ref().map_or(0, |left| left.height)
+    }
+
+    fn right_height(&self) -> usize {
+        self.right.as_ref().map_or(0, |right| right.height)
+    }
+
+    fn left_size(&self) -> usize {
+        self.left.as_ref().map_or(0, |left| left.size)
     }
 
-    fn get_mut(&mut self) -> &mut T {
-        &mut self.data
+    fn right_size(&self) -> usize {
+        self.right.as_ref().map_or(0, |right| right.size)
     }
 
-    fn take(self) -> T {
-        self.data
+    fn balance_factor(&self) -> isize {
+        self.left_height() as isize - self.right_height() as isize
+    }
+
+    fn update(&mut self) {
+        self.height = 1 + self.left_height().max(self.right_height());
+        self.size = 1 + self.left_size() + self.right_size();
     }
 }
 
-pub struct MyBST<T>
+/// An ordered map backed by a binary search tree.
+///
+/// Shares its nodes, optional AVL balancing and order-statistic queries with
+/// `MyBST`, which is a set view over `MyBSTMap<T, ()>`.
+pub struct MyBSTMap<K, V>
 where
-    T: Ord,
+    K: Ord,
 {
-    root: Option<Box<BSTNode<T>>>,
+    root: Option<Box<BSTNode<K, V>>>,
+    balanced: bool,
 }
 
-impl<T> MyBST<T>
+impl<K, V> MyBSTMap<K, V>
 where
-    T: Ord,
+    K: Ord,
 {
     pub fn new() -> Self {
-        Self { root: None }
+        Self { root: None, balanced: false }
+    }
+
+    /// Creates a self-balancing (AVL) map.
+    ///
+    /// Every insert and remove rotates the nodes on its path so that sibling
+    /// subtrees never differ in height by more than one, which keeps search,
+    /// insert and remove at O(log n) even for sorted input.
+    pub fn new_balanced() -> Self {
+        Self { root: None, balanced: true }
+    }
+
+    pub fn is_balanced(&self) -> bool {
+        self.balanced
+    }
+
+    pub fn len(&self) -> usize {
+        self.root.as_ref().map_or(0, |root| root.size)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Number of levels in the tree; 0 for an empty tree.
+    pub fn height(&self) -> usize {
+        self.root.as_ref().map_or(0, |root| root.height)
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+    }
+
+    //      node            pivot
+    //     /    \          /     \
+    //   pivot   c   ->   a      node
+    //   /   \                   /   \
+    //  a     b                 b     c
+    fn _right_rotate(mut node: Box<BSTNode<K, V>>) -> Box<BSTNode<K, V>> {
+        let mut pivot = node.left.take().expect("Right rotation without a left child.");
+        node.left = pivot.right.take();
+        node.update();
+        pivot.right = Some(node);
+        pivot.update();
+        pivot
+    }
+
+    //    node               pivot
+    //   /    \             /     \
+    //  a    pivot   ->   node     c
+    //       /   \       /   \
+    //      b     c     a     b
+    fn _left_rotate(mut node: Box<BSTNode<K, V>>) -> Box<BSTNode<K, V>> {
+        let mut pivot = node.right.take().expect("Left rotation without a right child.");
+        node.right = pivot.left.take();
+        node.update();
+        pivot.left = Some(node);
+        pivot.update();
+        pivot
+    }
+
+    // Refreshes the height of the subtree root after one of its children
+    // changed and, for balanced trees, restores the AVL invariant with at
+    // most two rotations.
+    fn _rebalance(node: &mut Option<Box<BSTNode<K, V>>>, balanced: bool) {
+        let Some(mut cur) = node.take() else {
+            return;
+        };
+        cur.update();
+
+        if balanced {
+            let balance = cur.balance_factor();
+            if balance > 1 {
+                if cur.left.as_ref().is_some_and(|left| left.balance_factor() < 0) {
+                    cur.left = cur.left.take().map(Self::_left_rotate);
+                }
+                cur = Self::_right_rotate(cur);
+            } else if balance < -1 {
+                if cur.right.as_ref().is_some_and(|right| right.balance_factor() > 0) {
+                    cur.right = cur.right.take().map(Self::_right_rotate);
+                }
+                cur = Self::_left_rotate(cur);
+            }
+        }
+
+        *node = Some(cur);
     }
 
-    fn _search_node(node: &BSTNode<T>, target: &T) -> bool {
-        match target.cmp(&node.data) {
+    fn _search_node<'n, Q>(node: &'n BSTNode<K, V>, target: &Q) -> Option<&'n BSTNode<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match target.cmp(node.key.borrow()) {
             Ordering::Equal => {
-                true
+                Some(node)
             }
             Ordering::Greater => {
                 match &node.right {
                     Some(right) => {
                         Self::_search_node(right, target)
                     }
-                    None => false,
+                    None => None,
                 }
             }
             Ordering::Less => {
@@ -69,59 +192,874 @@ where
                     Some(left) => {
                         Self::_search_node(left, target)
                     }
-                    None => false
+                    None => None
                 }
             }
         }
     }
 
-    pub fn search(&self, target: &T) -> bool {
-        match &self.root {
-            Some(node) => Self::_search_node(node, target),
-            None => false,
+    fn _search_node_mut<'n, Q>(node: &'n mut BSTNode<K, V>, target: &Q) -> Option<&'n mut BSTNode<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        match target.cmp(node.key.borrow()) {
+            Ordering::Equal => Some(node),
+            Ordering::Greater => Self::_search_node_mut(node.right.as_deref_mut()?, target),
+            Ordering::Less => Self::_search_node_mut(node.left.as_deref_mut()?, target),
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get_key_value(key).map(|(_, value)| value)
+    }
+
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let node = Self::_search_node(self.root.as_deref()?, key)?;
+        Some((&node.key, &node.value))
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        Self::_search_node_mut(self.root.as_deref_mut()?, key).map(|node| &mut node.value)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get_key_value(key).is_some()
+    }
+
+    // Returns the rank of `key` within the subtree, plus the value it replaced.
+    // Rotations keep the in-order position, so `_select_node_mut` finds the
+    // node again once rebalancing is done.
+    fn _insert_node(
+        node: &mut Option<Box<BSTNode<K, V>>>,
+        key: K,
+        value: V,
+        balanced: bool,
+    ) -> (usize, Option<V>) {
+        let (rank, replaced) = match node {
+            None => {
+                *node = Some(Box::new(BSTNode::new(key, value)));
+                return (0, None);
+            }
+            Some(cur) => match key.cmp(&cur.key) {
+                Ordering::Equal => {
+                    let replaced = mem::replace(&mut cur.value, value);
+                    return (cur.left_size(), Some(replaced));
+                }
+                Ordering::Greater => {
+                    let skipped = cur.left_size() + 1;
+                    let (rank, replaced) = Self::_insert_node(&mut cur.right, key, value, balanced);
+                    (skipped + rank, replaced)
+                }
+                Ordering::Less => Self::_insert_node(&mut cur.left, key, value, balanced),
+            },
+        };
+        if replaced.is_none() {
+            Self::_rebalance(node, balanced);
+        }
+        (rank, replaced)
+    }
+
+    // The node with the `k`-th smallest key, counting from 0.
+    fn _select_node_mut(&mut self, mut k: usize) -> Option<&mut BSTNode<K, V>> {
+        let mut cur = self.root.as_deref_mut();
+        while let Some(node) = cur {
+            let left_size = node.left_size();
+            match k.cmp(&left_size) {
+                Ordering::Equal => return Some(node),
+                Ordering::Less => cur = node.left.as_deref_mut(),
+                Ordering::Greater => {
+                    k -= left_size + 1;
+                    cur = node.right.as_deref_mut();
+                }
+            }
+        }
+        None
+    }
+
+    /// Inserts `value` under `key` and returns the value previously stored there.
+    /// An existing key is kept as is; only its value is replaced.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        Self::_insert_node(&mut self.root, key, value, self.balanced).1
+    }
+
+    /// Gets the entry for `key` for in-place manipulation.
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        let node = self.root.as_deref_mut().and_then(|root| Self::_search_node_mut(root, &key));
+        match node.map(NonNull::from) {
+            Some(node) => Entry::Occupied(OccupiedEntry { map: self, node, key }),
+            None => Entry::Vacant(VacantEntry { map: self, key }),
+        }
+    }
+
+    // Detaches the leftmost node of the subtree, re-linking its right child
+    // into the slot it occupied.
+    fn _take_min(node: &mut Option<Box<BSTNode<K, V>>>, balanced: bool) -> Option<Box<BSTNode<K, V>>> {
+        let cur = node.as_mut()?;
+        if cur.left.is_some() {
+            let min = Self::_take_min(&mut cur.left, balanced);
+            Self::_rebalance(node, balanced);
+            return min;
+        }
+
+        let mut min = node.take()?;
+        *node = min.right.take();
+        Some(min)
+    }
+
+    fn _remove_node<Q>(node: &mut Option<Box<BSTNode<K, V>>>, target: &Q, balanced: bool) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let cur = node.as_mut()?;
+        let removed = match target.cmp(cur.key.borrow()) {
+            Ordering::Greater => Self::_remove_node(&mut cur.right, target, balanced),
+            Ordering::Less => Self::_remove_node(&mut cur.left, target, balanced),
+            Ordering::Equal => {
+                let mut removed = node.take()?;
+                *node = match (removed.left.take(), removed.right.take()) {
+                    (None, None) => None,
+                    (Some(left), None) => Some(left),
+                    (None, Some(right)) => Some(right),
+                    (Some(left), Some(right)) => {
+                        // two children: the in-order successor takes the removed node's place
+                        let mut right = Some(right);
+                        let mut successor = Self::_take_min(&mut right, balanced)?;
+                        successor.left = Some(left);
+                        successor.right = right;
+                        Some(successor)
+                    }
+                };
+                Some(removed.take())
+            }
+        };
+        if removed.is_some() {
+            Self::_rebalance(node, balanced);
+        }
+        removed
+    }
+
+    /// Removes `key` and returns its value, or `None` if it is not in the map.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    /// Removes `key` and returns the stored key together with its value.
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        Self::_remove_node(&mut self.root, key, self.balanced)
+    }
+
+    /// The entry with the smallest key.
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        let mut cur = self.root.as_deref()?;
+        while let Some(left) = cur.left.as_deref() {
+            cur = left;
+        }
+        Some((&cur.key, &cur.value))
+    }
+
+    /// The entry with the largest key.
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        let mut cur = self.root.as_deref()?;
+        while let Some(right) = cur.right.as_deref() {
+            cur = right;
+        }
+        Some((&cur.key, &cur.value))
+    }
+
+    /// The entry with the largest key less than or equal to `target`.
+    pub fn floor(&self, target: &K) -> Option<(&K, &V)> {
+        let mut best = None;
+        let mut cur = self.root.as_deref();
+        while let Some(node) = cur {
+            match target.cmp(&node.key) {
+                Ordering::Equal => return Some((&node.key, &node.value)),
+                Ordering::Less => cur = node.left.as_deref(),
+                Ordering::Greater => {
+                    best = Some((&node.key, &node.value));
+                    cur = node.right.as_deref();
+                }
+            }
+        }
+        best
+    }
+
+    /// The entry with the smallest key greater than or equal to `target`.
+    pub fn ceiling(&self, target: &K) -> Option<(&K, &V)> {
+        let mut best = None;
+        let mut cur = self.root.as_deref();
+        while let Some(node) = cur {
+            match target.cmp(&node.key) {
+                Ordering::Equal => return Some((&node.key, &node.value)),
+                Ordering::Greater => cur = node.right.as_deref(),
+                Ordering::Less => {
+                    best = Some((&node.key, &node.value));
+                    cur = node.left.as_deref();
+                }
+            }
+        }
+        best
+    }
+
+    /// Number of keys strictly less than `target`, whether or not `target` is in the map.
+    pub fn rank(&self, target: &K) -> usize {
+        let mut rank = 0;
+        let mut cur = self.root.as_deref();
+        while let Some(node) = cur {
+            match target.cmp(&node.key) {
+                Ordering::Equal => return rank + node.left_size(),
+                Ordering::Less => cur = node.left.as_deref(),
+                Ordering::Greater => {
+                    rank += node.left_size() + 1;
+                    cur = node.right.as_deref();
+                }
+            }
+        }
+        rank
+    }
+
+    /// The entry with the `k`-th smallest key, counting from 0.
+    pub fn select(&self, mut k: usize) -> Option<(&K, &V)> {
+        let mut cur = self.root.as_deref();
+        while let Some(node) = cur {
+            let left_size = node.left_size();
+            match k.cmp(&left_size) {
+                Ordering::Equal => return Some((&node.key, &node.value)),
+                Ordering::Less => cur = node.left.as_deref(),
+                Ordering::Greater => {
+                    k -= left_size + 1;
+                    cur = node.right.as_deref();
+                }
+            }
+        }
+        None
+    }
+
+    // Number of keys that sort before `bound` when it starts a range.
+    fn _count_before_start(&self, bound: Bound<&K>) -> usize {
+        match bound {
+            Bound::Included(start) => self.rank(start),
+            Bound::Excluded(start) => self.rank(start) + usize::from(self.contains_key(start)),
+            Bound::Unbounded => 0,
+        }
+    }
+
+    // Number of keys that sort no later than `bound` when it ends a range.
+    fn _count_until_end(&self, bound: Bound<&K>) -> usize {
+        match bound {
+            Bound::Included(end) => self.rank(end) + usize::from(self.contains_key(end)),
+            Bound::Excluded(end) => self.rank(end),
+            Bound::Unbounded => self.len(),
+        }
+    }
+
+    fn _count_between(&self, start: Bound<&K>, end: Bound<&K>) -> usize {
+        self._count_until_end(end).saturating_sub(self._count_before_start(start))
+    }
+
+    /// Number of keys inside `range`, in O(height).
+    pub fn count_range<R: RangeBounds<K>>(&self, range: R) -> usize {
+        self._count_between(range.start_bound(), range.end_bound())
+    }
+
+    fn _range_nodes<R: RangeBounds<K>>(&self, range: R) -> NodeRange<'_, K, V> {
+        let mut iter = NodeRange {
+            front: Vec::new(),
+            back: Vec::new(),
+            remaining: self._count_between(range.start_bound(), range.end_bound()),
+        };
+
+        let mut cur = self.root.as_deref();
+        while let Some(node) = cur {
+            let after_start = match range.start_bound() {
+                Bound::Included(start) => node.key >= *start,
+                Bound::Excluded(start) => node.key > *start,
+                Bound::Unbounded => true,
+            };
+            if after_start {
+                iter.front.push(node);
+                cur = node.left.as_deref();
+            } else {
+                cur = node.right.as_deref();
+            }
+        }
+
+        let mut cur = self.root.as_deref();
+        while let Some(node) = cur {
+            let before_end = match range.end_bound() {
+                Bound::Included(end) => node.key <= *end,
+                Bound::Excluded(end) => node.key < *end,
+                Bound::Unbounded => true,
+            };
+            if before_end {
+                iter.back.push(node);
+                cur = node.right.as_deref();
+            } else {
+                cur = node.left.as_deref();
+            }
+        }
+
+        iter
+    }
+
+    /// Ascending iterator over the entries whose keys are inside `range`.
+    /// Subtrees entirely outside the range are never visited.
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> MapRange<'_, K, V> {
+        MapRange { nodes: self._range_nodes(range) }
+    }
+
+    /// Iterator over the entries in ascending key order.
+    pub fn iter(&self) -> MapIter<'_, K, V> {
+        MapIter { nodes: NodeIter::new(self.root.as_deref()) }
+    }
+
+    /// Iterator over the entries in ascending key order, with mutable values.
+    pub fn iter_mut(&mut self) -> MapIterMut<'_, K, V> {
+        MapIterMut { nodes: NodeIterMut::new(self.root.as_deref_mut()) }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> + '_ {
+        self.iter_mut().map(|(_, value)| value)
+    }
+}
+
+impl<K, V> Default for MyBSTMap<K, V>
+where
+    K: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A view into a single entry of a `MyBSTMap`, returned by `MyBSTMap::entry`.
+pub enum Entry<'a, K, V>
+where
+    K: Ord,
+{
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+pub struct OccupiedEntry<'a, K, V>
+where
+    K: Ord,
+{
+    map: &'a mut MyBSTMap<K, V>,
+    // The map stays mutably borrowed for 'a, so nothing can move or free the
+    // node until the entry removes it.
+    node: NonNull<BSTNode<K, V>>,
+    // The probe key passed to `entry`, only used to find the node again on removal.
+    key: K,
+}
+
+pub struct VacantEntry<'a, K, V>
+where
+    K: Ord,
+{
+    map: &'a mut MyBSTMap<K, V>,
+    key: K,
+}
+
+impl<'a, K, V> Entry<'a, K, V>
+where
+    K: Ord,
+{
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Runs `f` on the value if the entry is occupied.
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
         }
+        self
     }
 }
 
-pub struct BSTIntoIter<T: Ord>
+impl<'a, K, V> OccupiedEntry<'a, K, V>
+where
+    K: Ord,
 {
-    tree: MyBST<T>,
-    stack: Vec<Box<BSTNode<T>>>,
+    /// The key stored in the map, not the one passed to `entry`.
+    pub fn key(&self) -> &K {
+        unsafe { &(*self.node.as_ptr()).key }
+    }
+
+    pub fn get(&self) -> &V {
+        unsafe { &(*self.node.as_ptr()).value }
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        unsafe { &mut (*self.node.as_ptr()).value }
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        unsafe { &mut (*self.node.as_ptr()).value }
+    }
+
+    /// Replaces the value and returns the old one.
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    pub fn remove_entry(self) -> (K, V) {
+        self.map.remove_entry(&self.key).expect("Occupied entry without a value.")
+    }
 }
 
-pub struct BSTIter<'a, T>
+impl<'a, K, V> VacantEntry<'a, K, V>
 where
-    T: Ord,
+    K: Ord,
 {
-    stack: Vec<&'a BSTNode<T>>,
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    pub fn insert(self, value: V) -> &'a mut V {
+        let map = self.map;
+        let (rank, _) = MyBSTMap::_insert_node(&mut map.root, self.key, value, map.balanced);
+        &mut map._select_node_mut(rank).expect("The node was just inserted.").value
+    }
 }
 
-pub struct BSTIterMut<'a, T>
+// In-order walk over the nodes, shared by the map and set iterators.
+struct NodeIter<'a, K, V>
 where
-    T: Ord,
+    K: Ord,
+{
+    stack: Vec<&'a BSTNode<K, V>>,
+}
+
+impl<'a, K: Ord, V> NodeIter<'a, K, V> {
+    fn new(root: Option<&'a BSTNode<K, V>>) -> Self {
+        let mut iter = NodeIter { stack: Vec::new() };
+        iter.push_left_spine(root);
+        iter
+    }
+
+    fn push_left_spine(&mut self, mut current: Option<&'a BSTNode<K, V>>) {
+        while let Some(node) = current {
+            self.stack.push(node);
+            current = node.left.as_deref();
+        }
+    }
+}
+
+impl<'a, K: Ord, V> Iterator for NodeIter<'a, K, V> {
+    type Item = &'a BSTNode<K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left_spine(node.right.as_deref());
+        Some(node)
+    }
+}
+
+// A node split into its key, value and right link, so its left subtree can
+// be borrowed independently.
+type SplitNodeMut<'a, K, V> = (&'a mut K, &'a mut V, Option<&'a mut BSTNode<K, V>>);
+
+// Mutable in-order walk handing out the key and value of each node.
+struct NodeIterMut<'a, K, V>
+where
+    K: Ord,
+{
+    stack: Vec<SplitNodeMut<'a, K, V>>,
+}
+
+impl<'a, K: Ord, V> NodeIterMut<'a, K, V> {
+    fn new(root: Option<&'a mut BSTNode<K, V>>) -> Self {
+        let mut iter = NodeIterMut { stack: Vec::new() };
+        iter.push_left_spine(root);
+        iter
+    }
+
+    fn push_left_spine(&mut self, mut current: Option<&'a mut BSTNode<K, V>>) {
+        while let Some(node) = current {
+            let BSTNode { key, value, left, right, .. } = node;
+            self.stack.push((key, value, right.as_deref_mut()));
+            current = left.as_deref_mut();
+        }
+    }
+}
+
+impl<'a, K: Ord, V> Iterator for NodeIterMut<'a, K, V> {
+    type Item = (&'a mut K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value, right) = self.stack.pop()?;
+        self.push_left_spine(right);
+        Some((key, value))
+    }
+}
+
+// Walks in from both ends of a range; `remaining` is the number of nodes in
+// the range that have not been yielded yet, so the two ends never cross.
+struct NodeRange<'a, K, V>
+where
+    K: Ord,
+{
+    front: Vec<&'a BSTNode<K, V>>,
+    back: Vec<&'a BSTNode<K, V>>,
+    remaining: usize,
+}
+
+impl<'a, K: Ord, V> Iterator for NodeRange<'a, K, V> {
+    type Item = &'a BSTNode<K, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = self.front.pop()?;
+        let mut cur = node.right.as_deref();
+        while let Some(next) = cur {
+            self.front.push(next);
+            cur = next.left.as_deref();
+        }
+        self.remaining -= 1;
+        Some(node)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K: Ord, V> DoubleEndedIterator for NodeRange<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = self.back.pop()?;
+        let mut cur = node.left.as_deref();
+        while let Some(prev) = cur {
+            self.back.push(prev);
+            cur = prev.right.as_deref();
+        }
+        self.remaining -= 1;
+        Some(node)
+    }
+}
+
+pub struct MapIter<'a, K, V>
+where
+    K: Ord,
+{
+    nodes: NodeIter<'a, K, V>,
+}
+
+impl<'a, K: Ord, V> Iterator for MapIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nodes.next().map(|node| (&node.key, &node.value))
+    }
+}
+
+pub struct MapIterMut<'a, K, V>
+where
+    K: Ord,
+{
+    nodes: NodeIterMut<'a, K, V>,
+}
+
+impl<'a, K: Ord, V> Iterator for MapIterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nodes.next().map(|(key, value)| (&*key, value))
+    }
+}
+
+/// Iterator returned by `MyBSTMap::range`.
+pub struct MapRange<'a, K, V>
+where
+    K: Ord,
 {
-    stack: Vec<&'a mut BSTNode<T>>,
+    nodes: NodeRange<'a, K, V>,
 }
 
-impl<T: Ord> BSTIntoIter<T> {
-    fn new(mut tree: MyBST<T>) -> Self {
-        let mut stack = Vec::new();
-        let mut current = tree.root.take();
+impl<'a, K: Ord, V> Iterator for MapRange<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nodes.next().map(|node| (&node.key, &node.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.nodes.size_hint()
+    }
+}
+
+impl<'a, K: Ord, V> ExactSizeIterator for MapRange<'a, K, V> {}
+
+impl<'a, K: Ord, V> DoubleEndedIterator for MapRange<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.nodes.next_back().map(|node| (&node.key, &node.value))
+    }
+}
+
+pub struct MapIntoIter<K, V>
+where
+    K: Ord,
+{
+    stack: Vec<Box<BSTNode<K, V>>>,
+}
+
+impl<K: Ord, V> MapIntoIter<K, V> {
+    fn new(mut map: MyBSTMap<K, V>) -> Self {
+        let mut iter = MapIntoIter { stack: Vec::new() };
+        iter.push_left_spine(map.root.take());
+        iter
+    }
+
+    fn push_left_spine(&mut self, mut current: Option<Box<BSTNode<K, V>>>) {
         while let Some(mut node) = current {
             current = node.left.take();
-            stack.push(node);
+            self.stack.push(node);
         }
+    }
+}
 
-        BSTIntoIter { tree, stack }
+impl<K: Ord, V> Iterator for MapIntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut node = self.stack.pop()?;
+        self.push_left_spine(node.right.take());
+        Some(node.take())
+    }
+}
+
+impl<K: Ord, V> IntoIterator for MyBSTMap<K, V> {
+    type Item = (K, V);
+    type IntoIter = MapIntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        MapIntoIter::new(self)
     }
 }
 
+impl<'a, K: Ord, V> IntoIterator for &'a MyBSTMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = MapIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K: Ord, V> IntoIterator for &'a mut MyBSTMap<K, V> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = MapIterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// An ordered set of values, the key-only view of a `MyBSTMap`.
+pub struct MyBST<T>
+where
+    T: Ord,
+{
+    map: MyBSTMap<T, ()>,
+}
+
+impl<T> MyBST<T>
+where
+    T: Ord,
+{
+    pub fn new() -> Self {
+        Self { map: MyBSTMap::new() }
+    }
+
+    /// Creates a self-balancing (AVL) tree; see `MyBSTMap::new_balanced`.
+    pub fn new_balanced() -> Self {
+        Self { map: MyBSTMap::new_balanced() }
+    }
+
+    pub fn is_balanced(&self) -> bool {
+        self.map.is_balanced()
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Number of levels in the tree; 0 for an empty tree.
+    pub fn height(&self) -> usize {
+        self.map.height()
+    }
+
+    pub fn search(&self, target: &T) -> bool {
+        self.map.contains_key(target)
+    }
+
+    /// Inserts `data` into the tree.
+    /// Returns `false` and drops `data` if an equal value is already present.
+    pub fn insert(&mut self, data: T) -> bool {
+        self.map.insert(data, ()).is_none()
+    }
+
+    /// Removes the value equal to `target` and returns it, or `None` if it is not in the tree.
+    pub fn remove(&mut self, target: &T) -> Option<T> {
+        self.map.remove_entry(target).map(|(data, _)| data)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.map.first_key_value().map(|(data, _)| data)
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        self.map.last_key_value().map(|(data, _)| data)
+    }
+
+    /// The largest value less than or equal to `target`.
+    pub fn floor(&self, target: &T) -> Option<&T> {
+        self.map.floor(target).map(|(data, _)| data)
+    }
+
+    /// The smallest value greater than or equal to `target`.
+    pub fn ceiling(&self, target: &T) -> Option<&T> {
+        self.map.ceiling(target).map(|(data, _)| data)
+    }
+
+    /// Number of values strictly less than `target`, whether or not `target` is in the tree.
+    pub fn rank(&self, target: &T) -> usize {
+        self.map.rank(target)
+    }
+
+    /// The `k`-th smallest value, counting from 0.
+    pub fn select(&self, k: usize) -> Option<&T> {
+        self.map.select(k).map(|(data, _)| data)
+    }
+
+    /// Number of values inside `range`, in O(height).
+    pub fn count_range<R: RangeBounds<T>>(&self, range: R) -> usize {
+        self.map.count_range(range)
+    }
+
+    /// Ascending iterator over the values inside `range`.
+    /// Subtrees entirely outside the range are never visited.
+    pub fn range<R: RangeBounds<T>>(&self, range: R) -> BSTRange<'_, T> {
+        BSTRange { nodes: self.map._range_nodes(range) }
+    }
+
+    /// In-order (ascending) iterator over the values.
+    pub fn iter(&self) -> BSTIter<'_, T> {
+        BSTIter { nodes: NodeIter::new(self.map.root.as_deref()) }
+    }
+
+    /// Visits each node before its left and right subtrees.
+    pub fn preorder(&self) -> BSTPreorderIter<'_, T> {
+        BSTPreorderIter {
+            stack: self.map.root.as_deref().into_iter().collect(),
+        }
+    }
+
+    /// Visits each node after its left and right subtrees.
+    pub fn postorder(&self) -> BSTPostorderIter<'_, T> {
+        BSTPostorderIter {
+            stack: self.map.root.as_deref().map(|node| (node, false)).into_iter().collect(),
+        }
+    }
+
+    /// Visits the nodes depth by depth, left to right.
+    pub fn level_order(&self) -> BSTLevelOrderIter<'_, T> {
+        BSTLevelOrderIter {
+            queue: self.map.root.as_deref().into_iter().collect(),
+        }
+    }
+}
+
+pub struct BSTIntoIter<T: Ord>
+{
+    entries: MapIntoIter<T, ()>,
+}
+
+pub struct BSTIter<'a, T>
+where
+    T: Ord,
+{
+    nodes: NodeIter<'a, T, ()>,
+}
+
 impl<T: Ord> IntoIterator for MyBST<T> {
     type Item = T;
     type IntoIter = BSTIntoIter<T>;
 
     fn into_iter(self) -> Self::IntoIter {
-        BSTIntoIter::new(self)
+        BSTIntoIter { entries: self.map.into_iter() }
     }
 }
 
@@ -132,16 +1070,113 @@ where
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let mut node = self.stack.pop()?;
-        let ret = node.data;
+        self.entries.next().map(|(data, _)| data)
+    }
+}
+
+impl<'a, T: Ord> Iterator for BSTIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nodes.next().map(|node| &node.key)
+    }
+}
+
+impl<'a, T: Ord> IntoIterator for &'a MyBST<T> {
+    type Item = &'a T;
+    type IntoIter = BSTIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator returned by `MyBST::range`.
+pub struct BSTRange<'a, T>
+where
+    T: Ord,
+{
+    nodes: NodeRange<'a, T, ()>,
+}
+
+impl<'a, T: Ord> Iterator for BSTRange<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nodes.next().map(|node| &node.key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.nodes.size_hint()
+    }
+}
+
+impl<'a, T: Ord> ExactSizeIterator for BSTRange<'a, T> {}
+
+impl<'a, T: Ord> DoubleEndedIterator for BSTRange<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.nodes.next_back().map(|node| &node.key)
+    }
+}
+
+pub struct BSTPreorderIter<'a, T>
+where
+    T: Ord,
+{
+    stack: Vec<&'a BSTNode<T, ()>>,
+}
+
+impl<'a, T: Ord> Iterator for BSTPreorderIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        // right goes first so the left subtree is popped first
+        self.stack.extend(node.right.as_deref());
+        self.stack.extend(node.left.as_deref());
+        Some(&node.key)
+    }
+}
+
+pub struct BSTPostorderIter<'a, T>
+where
+    T: Ord,
+{
+    // the flag marks nodes whose children have already been stacked
+    stack: Vec<(&'a BSTNode<T, ()>, bool)>,
+}
+
+impl<'a, T: Ord> Iterator for BSTPostorderIter<'a, T> {
+    type Item = &'a T;
 
-        let mut current = node.right.take();
-        while let Some(mut n) = current {
-            current = n.left.take();
-            self.stack.push(n);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, expanded) = self.stack.pop()?;
+            if expanded {
+                return Some(&node.key);
+            }
+            self.stack.push((node, true));
+            self.stack.extend(node.right.as_deref().map(|right| (right, false)));
+            self.stack.extend(node.left.as_deref().map(|left| (left, false)));
         }
+    }
+}
 
-        Some(ret)
+pub struct BSTLevelOrderIter<'a, T>
+where
+    T: Ord,
+{
+    queue: VecDeque<&'a BSTNode<T, ()>>,
+}
+
+impl<'a, T: Ord> Iterator for BSTLevelOrderIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+        self.queue.extend(node.left.as_deref());
+        self.queue.extend(node.right.as_deref());
+        Some(&node.key)
     }
 }
 
@@ -151,6 +1186,335 @@ where
     T: Ord,
 {
     fn default() -> Self {
-        Self { root: None }
+        Self::new()
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use super::{BSTNode, Entry, MyBST, MyBSTMap};
+    use std::cmp::Ordering;
+    use std::ops::Bound;
+
+    // Checks ordering, stored heights and the AVL balance of every subtree,
+    // returning the subtree height.
+    fn check_avl(node: &Option<Box<BSTNode<i32, ()>>>, low: Option<i32>, high: Option<i32>) -> usize {
+        let Some(node) = node else {
+            return 0;
+        };
+        assert!(low.is_none_or(|low| node.key > low));
+        assert!(high.is_none_or(|high| node.key < high));
+        let left = check_avl(&node.left, low, Some(node.key));
+        let right = check_avl(&node.right, Some(node.key), high);
+        assert!(left.abs_diff(right) <= 1, "unbalanced at {}", node.key);
+        assert_eq!(node.height, 1 + left.max(right));
+        assert_eq!(node.size, 1 + node.left_size() + node.right_size());
+        node.height
+    }
+
+    #[test]
+    fn order_statistics() {
+        let tree = tree_of(&[50, 30, 70, 20, 40, 60, 80]);
+        assert_eq!(tree.len(), 7);
+        assert_eq!((tree.min(), tree.max()), (Some(&20), Some(&80)));
+        assert_eq!(tree.floor(&45), Some(&40));
+        assert_eq!(tree.floor(&50), Some(&50));
+        assert_eq!(tree.floor(&10), None);
+        assert_eq!(tree.ceiling(&45), Some(&50));
+        assert_eq!(tree.ceiling(&81), None);
+        assert_eq!(tree.rank(&20), 0);
+        assert_eq!(tree.rank(&55), 4);
+        assert_eq!(tree.rank(&99), 7);
+        assert_eq!(tree.select(0), Some(&20));
+        assert_eq!(tree.select(4), Some(&60));
+        assert_eq!(tree.select(7), None);
+
+        let empty: MyBST<i32> = MyBST::new();
+        assert_eq!((empty.min(), empty.max(), empty.select(0)), (None, None, None));
+    }
+
+    #[test]
+    fn range_queries() {
+        let tree = tree_of(&[50, 30, 70, 20, 40, 60, 80, 35, 65]);
+        let collect = |iter: super::BSTRange<'_, i32>| iter.copied().collect::<Vec<_>>();
+        assert_eq!(collect(tree.range(35..65)), vec![35, 40, 50, 60]);
+        assert_eq!(collect(tree.range(35..=65)), vec![35, 40, 50, 60, 65]);
+        assert_eq!(collect(tree.range(..30)), vec![20]);
+        assert_eq!(collect(tree.range(66..)), vec![70, 80]);
+        assert_eq!(collect(tree.range(41..49)), Vec::<i32>::new());
+        assert_eq!(tree.range(..).rev().copied().collect::<Vec<_>>(), vec![80, 70, 65, 60, 50, 40, 35, 30, 20]);
+
+        let mut both_ends = tree.range(30..=70);
+        assert_eq!(both_ends.next(), Some(&30));
+        assert_eq!(both_ends.next_back(), Some(&70));
+        assert_eq!(both_ends.next_back(), Some(&65));
+        assert_eq!(both_ends.len(), 4);
+        assert_eq!(both_ends.copied().collect::<Vec<_>>(), vec![35, 40, 50, 60]);
+
+        assert_eq!(tree.count_range(35..65), 4);
+        assert_eq!(tree.count_range((Bound::Excluded(35), Bound::Included(80))), 6);
+        assert_eq!(tree.count_range(..), 9);
+        assert_eq!(tree.count_range((Bound::Included(60), Bound::Excluded(40))), 0);
+    }
+
+    #[test]
+    fn order_statistics_survive_removal_and_rotation() {
+        let mut tree = MyBST::new_balanced();
+        for x in 0..100 {
+            tree.insert(x * 2);
+        }
+        for x in (0..100).step_by(5) {
+            tree.remove(&(x * 2));
+        }
+        let values: Vec<i32> = tree.iter().copied().collect();
+        assert_eq!(tree.len(), values.len());
+        for (k, x) in values.iter().enumerate() {
+            assert_eq!(tree.select(k), Some(x));
+            assert_eq!(tree.rank(x), k);
+        }
+    }
+
+    #[test]
+    fn balanced_tree_stays_logarithmic_on_sorted_input() {
+        let mut tree = MyBST::new_balanced();
+        for x in 0..1_023 {
+            assert!(tree.insert(x));
+            check_avl(&tree.map.root, None, None);
+        }
+        assert_eq!(tree.height(), 10);
+        assert_eq!(tree.iter().copied().collect::<Vec<_>>(), (0..1_023).collect::<Vec<_>>());
+
+        let mut plain = MyBST::new();
+        for x in 0..100 {
+            plain.insert(x);
+        }
+        assert_eq!(plain.height(), 100);
+    }
+
+    #[test]
+    fn balanced_tree_rebalances_on_remove() {
+        let mut tree = MyBST::new_balanced();
+        for x in (0..200).rev() {
+            tree.insert(x);
+        }
+        for x in (0..200).filter(|x| x % 3 != 0) {
+            assert_eq!(tree.remove(&x), Some(x));
+            check_avl(&tree.map.root, None, None);
+        }
+        assert!(tree.height() <= 8);
+        assert_eq!(tree.iter().copied().collect::<Vec<_>>(), (0..200).step_by(3).collect::<Vec<_>>());
+    }
+
+    fn tree_of(values: &[i32]) -> MyBST<i32> {
+        let mut tree = MyBST::new();
+        for &x in values {
+            tree.insert(x);
+        }
+        tree
+    }
+
+    #[test]
+    fn traversal_orders() {
+        //        5
+        //      /   \
+        //     3     8
+        //    / \   / \
+        //   1   4 7   9
+        let tree = tree_of(&[5, 3, 8, 1, 4, 7, 9]);
+        assert_eq!(tree.iter().copied().collect::<Vec<_>>(), vec![1, 3, 4, 5, 7, 8, 9]);
+        assert_eq!(tree.preorder().copied().collect::<Vec<_>>(), vec![5, 3, 1, 4, 8, 7, 9]);
+        assert_eq!(tree.postorder().copied().collect::<Vec<_>>(), vec![1, 4, 3, 7, 9, 8, 5]);
+        assert_eq!(tree.level_order().copied().collect::<Vec<_>>(), vec![5, 3, 8, 1, 4, 7, 9]);
+        assert_eq!((&tree).into_iter().count(), 7);
+        assert_eq!(MyBST::<i32>::new().postorder().next(), None);
+    }
+
+    #[test]
+    fn deep_tree_does_not_overflow() {
+        let mut tree = MyBST::new();
+        for x in 0..5_000 {
+            tree.insert(x);
+        }
+        assert_eq!(tree.iter().count(), 5_000);
+        assert_eq!(tree.preorder().next(), Some(&0));
+        assert_eq!(tree.postorder().next(), Some(&4_999));
+        assert_eq!(tree.level_order().last(), Some(&4_999));
+        assert_eq!(tree.into_iter().last(), Some(4_999));
+    }
+
+    #[test]
+    fn insert_and_search() {
+        let mut tree = MyBST::new();
+        assert!(tree.is_empty());
+        for x in [5, 3, 8, 1, 4, 7, 9] {
+            assert!(tree.insert(x));
+        }
+        assert!(!tree.insert(4));
+        assert!(tree.search(&7));
+        assert!(!tree.search(&6));
+        assert_eq!(tree.into_iter().collect::<Vec<_>>(), vec![1, 3, 4, 5, 7, 8, 9]);
+    }
+
+    #[test]
+    fn remove_leaf_one_child_and_two_children() {
+        let mut tree = MyBST::new();
+        for x in [5, 3, 8, 1, 4, 7, 9, 6] {
+            tree.insert(x);
+        }
+        // leaf
+        assert_eq!(tree.remove(&1), Some(1));
+        // one child (7 -> 6)
+        assert_eq!(tree.remove(&7), Some(7));
+        // two children, successor is 6
+        assert_eq!(tree.remove(&5), Some(5));
+        assert_eq!(tree.remove(&5), None);
+        assert!(!tree.search(&5));
+        assert_eq!(tree.into_iter().collect::<Vec<_>>(), vec![3, 4, 6, 8, 9]);
+    }
+
+    #[test]
+    fn remove_until_empty() {
+        let mut tree = MyBST::new();
+        for x in [2, 1, 3] {
+            tree.insert(x);
+        }
+        assert_eq!(tree.remove(&2), Some(2));
+        assert_eq!(tree.remove(&1), Some(1));
+        assert_eq!(tree.remove(&3), Some(3));
+        assert!(tree.is_empty());
+        assert_eq!(tree.remove(&3), None);
+    }
+
+    #[test]
+    fn map_insert_get_remove() {
+        let mut map = MyBSTMap::new();
+        assert_eq!(map.insert("b", 2), None);
+        assert_eq!(map.insert("a", 1), None);
+        assert_eq!(map.insert("c", 3), None);
+        assert_eq!(map.insert("b", 20), Some(2));
+        assert_eq!(map.len(), 3);
+
+        assert_eq!(map.get("b"), Some(&20));
+        assert!(map.contains_key("c"));
+        *map.get_mut("a").unwrap() += 10;
+        assert_eq!(map.get_key_value("a"), Some((&"a", &11)));
+        assert_eq!(map.first_key_value(), Some((&"a", &11)));
+        assert_eq!(map.select(2), Some((&"c", &3)));
+
+        assert_eq!(map.remove("b"), Some(20));
+        assert_eq!(map.remove("b"), None);
+        assert_eq!(map.remove_entry("c"), Some(("c", 3)));
+        assert_eq!(map.get("z"), None);
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn map_entry_api() {
+        let mut counts: MyBSTMap<char, usize> = MyBSTMap::new_balanced();
+        for c in "mississippi".chars() {
+            *counts.entry(c).or_insert(0) += 1;
+        }
+        assert_eq!(counts.iter().collect::<Vec<_>>(), vec![(&'i', &4), (&'m', &1), (&'p', &2), (&'s', &4)]);
+
+        counts.entry('m').and_modify(|n| *n *= 10).or_default();
+        counts.entry('z').and_modify(|n| *n *= 10).or_insert_with(|| 7);
+        assert_eq!(counts.get(&'m'), Some(&10));
+        assert_eq!(counts.get(&'z'), Some(&7));
+
+        match counts.entry('p') {
+            Entry::Occupied(mut entry) => {
+                assert_eq!(entry.insert(5), 2);
+                assert_eq!(*entry.get(), 5);
+                assert_eq!(entry.remove(), 5);
+            }
+            Entry::Vacant(_) => panic!("'p' should be present"),
+        }
+        match counts.entry('q') {
+            Entry::Vacant(entry) => assert_eq!(entry.into_key(), 'q'),
+            Entry::Occupied(_) => panic!("'q' should be absent"),
+        }
+        assert!(!counts.contains_key(&'p'));
+        assert!(!counts.contains_key(&'q'));
+    }
+
+    #[test]
+    fn vacant_insert_survives_rotations() {
+        let mut map = MyBSTMap::new_balanced();
+        map.entry(10).or_insert(1);
+        map.entry(30).or_insert(3);
+        // inserting 20 under 30 rotates the tree twice
+        *map.entry(20).or_insert(5) += 1;
+        assert_eq!(map.get(&20), Some(&6));
+        for key in (0..64).rev() {
+            *map.entry(key).or_insert(key) += 100;
+        }
+        let expected = |key| match key {
+            10 => 101,
+            20 => 106,
+            30 => 103,
+            _ => key + 100,
+        };
+        assert!((0..64).all(|key| map.get(&key) == Some(&expected(key))));
+        assert_eq!(map.height(), 7);
+    }
+
+    #[test]
+    fn occupied_entry_uses_the_stored_key() {
+        // ordered by the number only, so a probe can differ from the stored key
+        #[derive(Debug)]
+        struct Tagged(i32, &'static str);
+        impl PartialEq for Tagged {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl Eq for Tagged {}
+        impl PartialOrd for Tagged {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Tagged {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let mut map = MyBSTMap::new_balanced();
+        for i in 0..8 {
+            map.insert(Tagged(i, "stored"), i * 10);
+        }
+        let Entry::Occupied(mut entry) = map.entry(Tagged(5, "probe")) else {
+            panic!("5 should be present");
+        };
+        assert_eq!(entry.key().1, "stored");
+        *entry.get_mut() += 1;
+        assert_eq!(entry.insert(7), 51);
+        assert_eq!(*entry.get(), 7);
+        let (key, value) = entry.remove_entry();
+        assert_eq!((key.1, value), ("stored", 7));
+        assert_eq!(map.entry(Tagged(3, "probe")).key().1, "stored");
+        assert_eq!(map.entry(Tagged(9, "probe")).key().1, "probe");
+        assert_eq!(map.len(), 7);
+    }
+
+    #[test]
+    fn map_iterators() {
+        let mut map = MyBSTMap::new();
+        for (k, v) in [(3, "c"), (1, "a"), (2, "b"), (4, "d")] {
+            map.insert(k, v.to_string());
+        }
+        for (k, v) in &mut map {
+            v.push_str(&k.to_string());
+        }
+        for v in map.values_mut() {
+            v.make_ascii_uppercase();
+        }
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(map.values().cloned().collect::<Vec<_>>(), vec!["A1", "B2", "C3", "D4"]);
+        assert_eq!(map.range(2..).rev().map(|(k, _)| *k).collect::<Vec<_>>(), vec![4, 3, 2]);
+        assert_eq!(map.count_range(..=2), 2);
+        assert_eq!(map.into_iter().map(|(k, _)| k).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
     }
 }
@@ -0,0 +1,405 @@
+// Port of the `MyGraph` stub in scripts/helloworld.py: every node keeps an
+// adjacency table of outgoing `Edge`s, each with a `to` node and a `weight`.
+
+use std::mem;
+
+use crate::project_errors::CollectionError;
+
+/// Identifies a node of a `Graph`. IDs are never reused, so an ID stays
+/// valid (or stays dangling) across other insertions and removals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// A dense index below `Graph::node_bound`, for per-node arrays.
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphKind {
+    Directed,
+    Undirected,
+}
+
+/// An adjacency table entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge<E> {
+    pub to: NodeId,
+    pub weight: E,
+}
+
+#[derive(Debug, Clone)]
+struct NodeEntry<N, E> {
+    data: N,
+    adjacency: Vec<Edge<E>>,
+    // only tracked for directed graphs
+    in_degree: usize,
+}
+
+/// A graph stored as adjacency lists, with at most one edge per ordered pair
+/// of nodes (per unordered pair when undirected).
+///
+/// An undirected edge is stored in the adjacency tables of both ends, so
+/// `neighbors` works the same in both modes.
+#[derive(Debug, Clone)]
+pub struct Graph<N, E> {
+    kind: GraphKind,
+    nodes: Vec<Option<NodeEntry<N, E>>>,
+    node_count: usize,
+    edge_count: usize,
+}
+
+fn unknown_node(operation: &'static str) -> CollectionError {
+    CollectionError::InvalidHandle { operation }
+}
+
+impl<N, E> Graph<N, E> {
+    pub fn new(kind: GraphKind) -> Self {
+        Self {
+            kind,
+            nodes: Vec::new(),
+            node_count: 0,
+            edge_count: 0,
+        }
+    }
+
+    pub fn directed() -> Self {
+        Self::new(GraphKind::Directed)
+    }
+
+    pub fn undirected() -> Self {
+        Self::new(GraphKind::Undirected)
+    }
+
+    pub fn kind(&self) -> GraphKind {
+        self.kind
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.kind == GraphKind::Directed
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// One past the largest node index ever handed out.
+    pub fn node_bound(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn add_node(&mut self, data: N) -> NodeId {
+        self.nodes.push(Some(NodeEntry {
+            data,
+            adjacency: Vec::new(),
+            in_degree: 0,
+        }));
+        self.node_count += 1;
+        NodeId(self.nodes.len() - 1)
+    }
+
+    /// Removes a node with all of its edges and returns its data.
+    pub fn remove_node(&mut self, id: NodeId) -> Option<N> {
+        let entry = self.nodes.get_mut(id.0)?.take()?;
+        self.node_count -= 1;
+        self.edge_count -= entry.adjacency.len();
+        match self.kind {
+            GraphKind::Directed => {
+                // a self-loop was counted among the outgoing edges already
+                let self_loops = entry.adjacency.iter().filter(|edge| edge.to == id).count();
+                self.edge_count -= entry.in_degree - self_loops;
+                for edge in &entry.adjacency {
+                    if let Some(target) = self.entry_mut(edge.to) {
+                        target.in_degree -= 1;
+                    }
+                }
+                if entry.in_degree > self_loops {
+                    for source in self.nodes.iter_mut().flatten() {
+                        source.adjacency.retain(|edge| edge.to != id);
+                    }
+                }
+            }
+            GraphKind::Undirected => {
+                for edge in &entry.adjacency {
+                    if let Some(neighbor) = self.entry_mut(edge.to) {
+                        neighbor.adjacency.retain(|back| back.to != id);
+                    }
+                }
+            }
+        }
+        Some(entry.data)
+    }
+
+    /// Removes the edge from `from` to `to` and returns its weight.
+    pub fn remove_edge(&mut self, from: NodeId, to: NodeId) -> Option<E> {
+        let source = self.entry_mut(from)?;
+        let pos = source.adjacency.iter().position(|edge| edge.to == to)?;
+        let edge = source.adjacency.remove(pos);
+        let kind = self.kind;
+        let target = self.entry_mut(to).expect("An edge points at a live node.");
+        match kind {
+            GraphKind::Directed => target.in_degree -= 1,
+            GraphKind::Undirected if from != to => target.adjacency.retain(|back| back.to != from),
+            GraphKind::Undirected => {}
+        }
+        self.edge_count -= 1;
+        Some(edge.weight)
+    }
+
+    pub fn contains_node(&self, id: NodeId) -> bool {
+        self.entry(id).is_some()
+    }
+
+    pub fn contains_edge(&self, from: NodeId, to: NodeId) -> bool {
+        self.edge(from, to).is_some()
+    }
+
+    pub fn node(&self, id: NodeId) -> Option<&N> {
+        self.entry(id).map(|entry| &entry.data)
+    }
+
+    pub fn node_mut(&mut self, id: NodeId) -> Option<&mut N> {
+        self.entry_mut(id).map(|entry| &mut entry.data)
+    }
+
+    /// The weight of the edge from `from` to `to`.
+    pub fn edge(&self, from: NodeId, to: NodeId) -> Option<&E> {
+        self.entry(from)?
+            .adjacency
+            .iter()
+            .find(|edge| edge.to == to)
+            .map(|edge| &edge.weight)
+    }
+
+    /// The adjacency table of a node: the edges leaving it, in insertion
+    /// order. Empty for an unknown node.
+    pub fn neighbors(&self, id: NodeId) -> impl Iterator<Item = &Edge<E>> {
+        self.entry(id).into_iter().flat_map(|entry| entry.adjacency.iter())
+    }
+
+    pub fn out_degree(&self, id: NodeId) -> Option<usize> {
+        self.entry(id).map(|entry| entry.adjacency.len())
+    }
+
+    /// The number of edges ending at a node; the same as `out_degree` for an
+    /// undirected graph.
+    pub fn in_degree(&self, id: NodeId) -> Option<usize> {
+        self.entry(id).map(|entry| match self.kind {
+            GraphKind::Directed => entry.in_degree,
+            GraphKind::Undirected => entry.adjacency.len(),
+        })
+    }
+
+    /// The live nodes in ID order.
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeId, &N)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| entry.as_ref().map(|entry| (NodeId(index), &entry.data)))
+    }
+
+    pub fn node_ids(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.nodes().map(|(id, _)| id)
+    }
+
+    /// Every edge as `(from, to, weight)`, grouped by source node. An
+    /// undirected edge is reported once, from its lower-ID end.
+    pub fn edges(&self) -> impl Iterator<Item = (NodeId, NodeId, &E)> {
+        let directed = self.is_directed();
+        self.nodes.iter().enumerate().flat_map(move |(index, entry)| {
+            let from = NodeId(index);
+            entry
+                .iter()
+                .flat_map(|entry| entry.adjacency.iter())
+                .filter(move |edge| directed || from <= edge.to)
+                .map(move |edge| (from, edge.to, &edge.weight))
+        })
+    }
+
+    /// Removes every node and edge. IDs handed out before stay dangling.
+    pub fn clear(&mut self) {
+        self.nodes.iter_mut().for_each(|slot| *slot = None);
+        self.node_count = 0;
+        self.edge_count = 0;
+    }
+
+    fn entry(&self, id: NodeId) -> Option<&NodeEntry<N, E>> {
+        self.nodes.get(id.0)?.as_ref()
+    }
+
+    fn entry_mut(&mut self, id: NodeId) -> Option<&mut NodeEntry<N, E>> {
+        self.nodes.get_mut(id.0)?.as_mut()
+    }
+}
+
+impl<N, E: Clone> Graph<N, E> {
+    /// Adds an edge, or re-weights it if it exists and returns the old
+    /// weight. Both ends must be live nodes.
+    ///
+    /// Only undirected graphs clone `weight`, to store it at both ends.
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: E) -> Result<Option<E>, CollectionError> {
+        if !self.contains_node(from) || !self.contains_node(to) {
+            return Err(unknown_node("add_edge"));
+        }
+        let kind = self.kind;
+        let mirrored = kind == GraphKind::Undirected && from != to;
+        let (weight, back_weight) = if mirrored {
+            (weight.clone(), Some(weight))
+        } else {
+            (weight, None)
+        };
+
+        let source = self.entry_mut(from).expect("Checked above.");
+        if let Some(edge) = source.adjacency.iter_mut().find(|edge| edge.to == to) {
+            let old = mem::replace(&mut edge.weight, weight);
+            if let Some(back_weight) = back_weight {
+                let target = self.entry_mut(to).expect("Checked above.");
+                let back = target.adjacency.iter_mut().find(|edge| edge.to == from);
+                back.expect("An undirected edge is stored at both ends.").weight = back_weight;
+            }
+            return Ok(Some(old));
+        }
+
+        source.adjacency.push(Edge { to, weight });
+        let target = self.entry_mut(to).expect("Checked above.");
+        match back_weight {
+            Some(back_weight) => target.adjacency.push(Edge { to: from, weight: back_weight }),
+            None if kind == GraphKind::Directed => target.in_degree += 1,
+            None => {}
+        }
+        self.edge_count += 1;
+        Ok(None)
+    }
+}
+
+impl<N, E> Default for Graph<N, E> {
+    /// An empty directed graph.
+    fn default() -> Self {
+        Self::directed()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Graph, NodeId};
+    use crate::project_errors::CollectionError;
+
+    fn targets<N, E>(graph: &Graph<N, E>, id: NodeId) -> Vec<NodeId> {
+        graph.neighbors(id).map(|edge| edge.to).collect()
+    }
+
+    #[test]
+    fn directed_graph_tracks_edges_and_degrees() {
+        let mut graph: Graph<&str, u32> = Graph::directed();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let c = graph.add_node("c");
+        assert_eq!(graph.add_edge(a, b, 1), Ok(None));
+        assert_eq!(graph.add_edge(a, c, 4), Ok(None));
+        assert_eq!(graph.add_edge(b, c, 2), Ok(None));
+        assert_eq!(graph.add_edge(c, c, 7), Ok(None));
+        assert_eq!(graph.add_edge(a, c, 3), Ok(Some(4)));
+
+        assert_eq!((graph.node_count(), graph.edge_count()), (3, 4));
+        assert_eq!(targets(&graph, a), vec![b, c]);
+        assert_eq!(graph.edge(a, c), Some(&3));
+        assert_eq!(graph.edge(c, a), None);
+        assert_eq!((graph.out_degree(a), graph.in_degree(a)), (Some(2), Some(0)));
+        assert_eq!((graph.out_degree(c), graph.in_degree(c)), (Some(1), Some(3)));
+        let edges: Vec<_> = graph.edges().map(|(from, to, w)| (from, to, *w)).collect();
+        assert_eq!(edges, vec![(a, b, 1), (a, c, 3), (b, c, 2), (c, c, 7)]);
+
+        assert_eq!(graph.remove_edge(b, c), Some(2));
+        assert_eq!(graph.remove_edge(b, c), None);
+        assert_eq!(graph.in_degree(c), Some(2));
+        assert_eq!(graph.edge_count(), 3);
+    }
+
+    #[test]
+    fn undirected_edges_are_stored_at_both_ends() {
+        let mut graph: Graph<(), f64> = Graph::undirected();
+        let ids: Vec<NodeId> = (0..4).map(|_| graph.add_node(())).collect();
+        graph.add_edge(ids[0], ids[1], 1.5).unwrap();
+        graph.add_edge(ids[2], ids[0], 2.0).unwrap();
+        graph.add_edge(ids[3], ids[3], 0.5).unwrap();
+        assert_eq!(graph.add_edge(ids[1], ids[0], 3.0), Ok(Some(1.5)));
+
+        assert_eq!(graph.edge(ids[0], ids[1]), Some(&3.0));
+        assert_eq!(targets(&graph, ids[0]), vec![ids[1], ids[2]]);
+        assert_eq!(graph.in_degree(ids[0]), Some(2));
+        assert_eq!(graph.edge_count(), 3);
+        let edges: Vec<_> = graph.edges().map(|(from, to, _)| (from, to)).collect();
+        assert_eq!(edges, vec![(ids[0], ids[1]), (ids[0], ids[2]), (ids[3], ids[3])]);
+
+        assert_eq!(graph.remove_edge(ids[0], ids[2]), Some(2.0));
+        assert!(!graph.contains_edge(ids[2], ids[0]));
+        assert_eq!(graph.remove_edge(ids[3], ids[3]), Some(0.5));
+        assert_eq!(graph.edge_count(), 1);
+    }
+
+    #[test]
+    fn removing_nodes_keeps_ids_stable() {
+        for mut graph in [Graph::directed(), Graph::undirected()] {
+            let ids: Vec<NodeId> = (0..5).map(|i| graph.add_node(i * 10)).collect();
+            for (from, to) in [(0, 1), (1, 2), (2, 0), (2, 2), (3, 2), (4, 0)] {
+                graph.add_edge(ids[from], ids[to], ()).unwrap();
+            }
+            assert_eq!(graph.remove_node(ids[2]), Some(20));
+            assert_eq!(graph.remove_node(ids[2]), None);
+            assert_eq!(graph.node_count(), 4);
+            assert_eq!(graph.edge_count(), 2);
+            assert_eq!(graph.edges().count(), 2);
+            assert_eq!(graph.out_degree(ids[3]), Some(0));
+            assert_eq!(graph.in_degree(ids[2]), None);
+            assert!(graph.nodes().all(|(id, _)| graph.neighbors(id).all(|edge| edge.to != ids[2])));
+
+            let new = graph.add_node(50);
+            assert_eq!(new.index(), 5);
+            assert_eq!(graph.node(ids[4]), Some(&40));
+            assert_eq!(graph.node_ids().collect::<Vec<_>>(), vec![ids[0], ids[1], ids[3], ids[4], new]);
+            assert_eq!(
+                graph.add_edge(ids[2], new, ()),
+                Err(CollectionError::InvalidHandle { operation: "add_edge" })
+            );
+        }
+    }
+
+    #[test]
+    fn only_mirrored_edges_clone_the_weight() {
+        let weight = std::rc::Rc::new(());
+        let mut directed = Graph::directed();
+        let mut undirected = Graph::undirected();
+        let (a, b) = (directed.add_node(()), directed.add_node(()));
+        let (c, d) = (undirected.add_node(()), undirected.add_node(()));
+        directed.add_edge(a, b, weight.clone()).unwrap();
+        directed.add_edge(a, b, weight.clone()).unwrap();
+        undirected.add_edge(c, c, weight.clone()).unwrap();
+        assert_eq!(std::rc::Rc::strong_count(&weight), 3);
+        undirected.add_edge(c, d, weight.clone()).unwrap();
+        assert_eq!(std::rc::Rc::strong_count(&weight), 5);
+        assert_eq!(undirected.edge_count(), 2);
+    }
+
+    #[test]
+    fn clear_keeps_old_ids_dangling() {
+        let mut graph: Graph<&str, ()> = Graph::undirected();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        graph.add_edge(a, b, ()).unwrap();
+        graph.clear();
+        assert_eq!((graph.node_count(), graph.edge_count()), (0, 0));
+        assert_eq!(graph.node(a), None);
+
+        let c = graph.add_node("c");
+        assert_ne!(c, a);
+        assert!(!graph.contains_node(a) && !graph.contains_node(b));
+        assert_eq!(graph.add_edge(a, c, ()), Err(CollectionError::InvalidHandle { operation: "add_edge" }));
+        assert_eq!(graph.node_ids().collect::<Vec<_>>(), vec![c]);
+    }
+}
@@ -0,0 +1,421 @@
+// Binary heap over a `Vec`, ordered by a pluggable comparator.
+
+use std::cmp::Ordering;
+use std::fmt::{self, Debug};
+use std::mem;
+use std::ops::{Deref, DerefMut};
+
+/// Decides the order of a `MyHeap`: the greatest element is on top.
+pub trait Compare<T> {
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+/// Orders by `Ord`, so the heap pops the largest element first.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MaxComparator;
+
+/// Orders by reversed `Ord`, so the heap pops the smallest element first.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinComparator;
+
+/// Orders by a closure, e.g. `FnComparator(|a: &Job, b: &Job| a.deadline.cmp(&b.deadline))`.
+#[derive(Clone, Copy)]
+pub struct FnComparator<F>(pub F);
+
+impl<T: Ord> Compare<T> for MaxComparator {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+impl<T: Ord> Compare<T> for MinComparator {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+impl<T, F> Compare<T> for FnComparator<F>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        (self.0)(a, b)
+    }
+}
+
+pub struct MyHeap<T, C: Compare<T> = MaxComparator> {
+    data: Vec<T>,
+    cmp: C,
+}
+
+impl<T: Ord> MyHeap<T, MaxComparator> {
+    /// An empty max-heap.
+    pub fn new() -> Self {
+        Self::with_comparator(MaxComparator)
+    }
+}
+
+impl<T: Ord> MyHeap<T, MinComparator> {
+    /// An empty min-heap.
+    pub fn new_min() -> Self {
+        Self::with_comparator(MinComparator)
+    }
+}
+
+impl<T, C: Compare<T>> MyHeap<T, C> {
+    pub fn with_comparator(cmp: C) -> Self {
+        Self { data: Vec::new(), cmp }
+    }
+
+    /// Builds a heap from `vec` in O(n).
+    pub fn from_vec(vec: Vec<T>, cmp: C) -> Self {
+        let mut heap = Self { data: vec, cmp };
+        heap.rebuild();
+        heap
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.data.push(item);
+        self.sift_up(self.data.len() - 1);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let mut item = self.data.pop()?;
+        if !self.data.is_empty() {
+            mem::swap(&mut item, &mut self.data[0]);
+            self.sift_down(0, self.data.len());
+        }
+        Some(item)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Mutable access to the top element; the heap is repaired when the
+    /// returned guard is dropped.
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, C>> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(PeekMut { heap: self, sift: false })
+    }
+
+    /// Iterates in arbitrary (heap) order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    /// Returns the elements in arbitrary (heap) order.
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    /// Returns the elements sorted ascending by the comparator, so the top of
+    /// the heap comes last.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut end = self.data.len();
+        while end > 1 {
+            end -= 1;
+            self.data.swap(0, end);
+            self.sift_down(0, end);
+        }
+        self.data
+    }
+
+    /// Moves all elements of `other` into this heap, leaving `other` empty.
+    pub fn append(&mut self, other: &mut Self) {
+        // the larger vector is kept, but if it is `other`'s it is only ordered
+        // by `other`'s comparator, which may disagree with ours
+        let swapped = self.len() < other.len();
+        if swapped {
+            mem::swap(&mut self.data, &mut other.data);
+        }
+        let start = self.data.len();
+        let added = other.data.len();
+        self.data.append(&mut other.data);
+
+        // rebuilding costs about 2n comparisons, sifting each new element up log n
+        let log_len = (usize::BITS - self.data.len().leading_zeros()) as usize;
+        if swapped || 2 * self.data.len() < added * log_len {
+            self.rebuild();
+        } else {
+            for pos in start..self.data.len() {
+                self.sift_up(pos);
+            }
+        }
+    }
+
+    /// Removes the elements in heap order, top first. Whatever is left when the
+    /// iterator is dropped is removed as well.
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T, C> {
+        DrainSorted { heap: self }
+    }
+
+    /// Keeps only the elements for which `keep` returns `true`.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let len = self.data.len();
+        self.data.retain(|item| keep(item));
+        if self.data.len() != len {
+            self.rebuild();
+        }
+    }
+
+    fn rebuild(&mut self) {
+        let len = self.data.len();
+        for pos in (0..len / 2).rev() {
+            self.sift_down(pos, len);
+        }
+    }
+
+    fn sift_up(&mut self, mut pos: usize) {
+        while pos > 0 {
+            let parent = (pos - 1) / 2;
+            if self.cmp.compare(&self.data[pos], &self.data[parent]) != Ordering::Greater {
+                break;
+            }
+            self.data.swap(pos, parent);
+            pos = parent;
+        }
+    }
+
+    // Sifts the element at `pos` down within `data[..end]`.
+    fn sift_down(&mut self, mut pos: usize, end: usize) {
+        loop {
+            let left = 2 * pos + 1;
+            if left >= end {
+                break;
+            }
+            let right = left + 1;
+            let child = if right < end && self.cmp.compare(&self.data[right], &self.data[left]) == Ordering::Greater {
+                right
+            } else {
+                left
+            };
+            if self.cmp.compare(&self.data[child], &self.data[pos]) != Ordering::Greater {
+                break;
+            }
+            self.data.swap(pos, child);
+            pos = child;
+        }
+    }
+}
+
+impl<T, C: Compare<T> + Default> Default for MyHeap<T, C> {
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<T, C: Compare<T> + Default> From<Vec<T>> for MyHeap<T, C> {
+    fn from(vec: Vec<T>) -> Self {
+        Self::from_vec(vec, C::default())
+    }
+}
+
+impl<T, C: Compare<T> + Default> FromIterator<T> for MyHeap<T, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_vec(iter.into_iter().collect(), C::default())
+    }
+}
+
+impl<T, C: Compare<T>> Extend<T> for MyHeap<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<T: Clone, C: Compare<T> + Clone> Clone for MyHeap<T, C> {
+    fn clone(&self) -> Self {
+        Self { data: self.data.clone(), cmp: self.cmp.clone() }
+    }
+}
+
+impl<T: Debug, C: Compare<T>> Debug for MyHeap<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Mutable access to the top of a `MyHeap`, see `MyHeap::peek_mut`.
+pub struct PeekMut<'a, T, C: Compare<T>> {
+    heap: &'a mut MyHeap<T, C>,
+    sift: bool,
+}
+
+impl<'a, T, C: Compare<T>> PeekMut<'a, T, C> {
+    /// Removes the peeked element from the heap.
+    pub fn pop(mut this: Self) -> T {
+        // nothing to repair, `pop` restores the heap itself
+        this.sift = false;
+        this.heap.pop().expect("PeekMut is only handed out for non-empty heaps.")
+    }
+}
+
+impl<'a, T, C: Compare<T>> Deref for PeekMut<'a, T, C> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.heap.data[0]
+    }
+}
+
+impl<'a, T, C: Compare<T>> DerefMut for PeekMut<'a, T, C> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.sift = true;
+        &mut self.heap.data[0]
+    }
+}
+
+impl<'a, T, C: Compare<T>> Drop for PeekMut<'a, T, C> {
+    fn drop(&mut self) {
+        if self.sift {
+            let len = self.heap.data.len();
+            self.heap.sift_down(0, len);
+        }
+    }
+}
+
+/// The iterator returned by `MyHeap::drain_sorted`.
+pub struct DrainSorted<'a, T, C: Compare<T>> {
+    heap: &'a mut MyHeap<T, C>,
+}
+
+impl<'a, T, C: Compare<T>> Iterator for DrainSorted<'a, T, C> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.heap.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+impl<'a, T, C: Compare<T>> ExactSizeIterator for DrainSorted<'a, T, C> {}
+
+impl<'a, T, C: Compare<T>> Drop for DrainSorted<'a, T, C> {
+    fn drop(&mut self) {
+        self.heap.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cmp::Ordering;
+
+    use super::{FnComparator, MinComparator, MyHeap, PeekMut};
+
+    // Checks the heap property of every parent/child pair.
+    fn check_heap<T: Ord>(heap: &MyHeap<T>) {
+        let data = &heap.data;
+        for child in 1..data.len() {
+            assert!(data[(child - 1) / 2] >= data[child]);
+        }
+    }
+
+    #[test]
+    fn max_and_min_heaps_pop_in_order() {
+        let mut heap = MyHeap::new();
+        for x in [5, 1, 8, 3, 9, 2, 8] {
+            heap.push(x);
+        }
+        check_heap(&heap);
+        assert_eq!((heap.len(), heap.peek()), (7, Some(&9)));
+        let popped: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(popped, vec![9, 8, 8, 5, 3, 2, 1]);
+        assert!(heap.is_empty());
+
+        let mut min_heap = MyHeap::new_min();
+        min_heap.extend([5, 1, 8, 3]);
+        assert_eq!(min_heap.pop(), Some(1));
+        assert_eq!(min_heap.peek(), Some(&3));
+    }
+
+    #[test]
+    fn from_vec_heapifies_and_sorts() {
+        let values = vec![11, 2, 5, 6, 7, 8, 9, 12, 4, 10, 1, 3, 15, 13, 14];
+        let heap = MyHeap::from(values.clone());
+        check_heap(&heap);
+        assert_eq!(heap.peek(), Some(&15));
+        let mut sorted = values.clone();
+        sorted.sort();
+        assert_eq!(heap.into_sorted_vec(), sorted);
+
+        let min_heap: MyHeap<i32, MinComparator> = values.iter().copied().collect();
+        assert_eq!(min_heap.into_sorted_vec(), sorted.into_iter().rev().collect::<Vec<_>>());
+        assert!(MyHeap::<i32>::new().into_sorted_vec().is_empty());
+    }
+
+    #[test]
+    fn peek_mut_sifts_on_drop() {
+        let mut heap = MyHeap::from(vec![10, 7, 8, 1]);
+        *heap.peek_mut().unwrap() = 0;
+        check_heap(&heap);
+        assert_eq!(heap.peek(), Some(&8));
+
+        {
+            let top = heap.peek_mut().unwrap();
+            assert_eq!(*top, 8);
+        }
+        let top = heap.peek_mut().unwrap();
+        assert_eq!(PeekMut::pop(top), 8);
+        assert_eq!(heap.into_sorted_vec(), vec![0, 1, 7]);
+        assert!(MyHeap::<i32>::new().peek_mut().is_none());
+    }
+
+    #[test]
+    fn append_drain_and_retain() {
+        let mut heap = MyHeap::from(vec![1, 5, 3]);
+        let mut small = MyHeap::from(vec![4]);
+        heap.append(&mut small);
+        let mut large = MyHeap::from((10..30).collect::<Vec<_>>());
+        heap.append(&mut large);
+        assert!(small.is_empty() && large.is_empty());
+        check_heap(&heap);
+        assert_eq!(heap.len(), 24);
+
+        heap.retain(|x| x % 2 == 1);
+        check_heap(&heap);
+        assert_eq!(heap.len(), 13);
+
+        let top_three: Vec<_> = heap.drain_sorted().take(3).collect();
+        assert_eq!(top_three, vec![29, 27, 25]);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn custom_comparator_orders_by_key() {
+        let by_len = FnComparator(|a: &&str, b: &&str| a.len().cmp(&b.len()).then_with(|| b.cmp(a)));
+        let mut heap = MyHeap::from_vec(vec!["pear", "fig", "banana", "kiwi"], by_len);
+        heap.push("apple");
+        assert_eq!(heap.drain_sorted().collect::<Vec<_>>(), vec!["banana", "apple", "kiwi", "pear", "fig"]);
+    }
+
+    #[test]
+    fn append_reorders_a_heap_built_by_another_comparator() {
+        type ByFn = FnComparator<fn(&i32, &i32) -> Ordering>;
+        let mut max: MyHeap<i32, ByFn> = MyHeap::from_vec(vec![3, 1], FnComparator(|a, b| a.cmp(b)));
+        let mut min: MyHeap<i32, ByFn> = MyHeap::from_vec((0..10).collect(), FnComparator(|a, b| b.cmp(a)));
+        max.append(&mut min);
+        assert_eq!(max.drain_sorted().collect::<Vec<_>>(), vec![9, 8, 7, 6, 5, 4, 3, 3, 2, 1, 1, 0]);
+    }
+}
@@ -0,0 +1,593 @@
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Color {
+    Red,
+    Black,
+}
+
+type Link<T> = Option<NonNull<RbNode<T>>>;
+
+struct RbNode<T> {
+    data: T,
+    color: Color,
+    parent: Link<T>,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+impl<T> RbNode<T> {
+    fn new(data: T, parent: Link<T>) -> Self {
+        Self {
+            data,
+            color: Color::Red,
+            parent,
+            left: None,
+            right: None,
+        }
+    }
+}
+
+// Missing children count as black leaves.
+fn color<T>(link: Link<T>) -> Color {
+    link.map_or(Color::Black, |node| unsafe { node.as_ref().color })
+}
+
+fn set_color<T>(link: Link<T>, color: Color) {
+    if let Some(node) = link {
+        unsafe { (*node.as_ptr()).color = color }
+    }
+}
+
+/// A red-black tree.
+///
+/// Offers the same operations as `MyBST`, but keeps itself balanced with at
+/// most two rotations per insert and three per remove, which makes it the
+/// cheaper choice for write-heavy workloads than the AVL mode of `MyBST`.
+pub struct RbTree<T>
+where
+    T: Ord,
+{
+    root: Link<T>,
+    size: usize,
+    _marker: PhantomData<Box<RbNode<T>>>,
+}
+
+impl<T> RbTree<T>
+where
+    T: Ord,
+{
+    pub fn new() -> Self {
+        Self {
+            root: None,
+            size: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    fn _find(&self, target: &T) -> Link<T> {
+        let mut cur = self.root;
+        while let Some(node) = cur {
+            let node = unsafe { node.as_ref() };
+            cur = match target.cmp(&node.data) {
+                Ordering::Equal => break,
+                Ordering::Greater => node.right,
+                Ordering::Less => node.left,
+            };
+        }
+        cur
+    }
+
+    pub fn search(&self, target: &T) -> bool {
+        self._find(target).is_some()
+    }
+
+    fn _leftmost(mut node: NonNull<RbNode<T>>) -> NonNull<RbNode<T>> {
+        while let Some(left) = unsafe { node.as_ref().left } {
+            node = left;
+        }
+        node
+    }
+
+    fn _rightmost(mut node: NonNull<RbNode<T>>) -> NonNull<RbNode<T>> {
+        while let Some(right) = unsafe { node.as_ref().right } {
+            node = right;
+        }
+        node
+    }
+
+    pub fn min(&self) -> Option<&T> {
+        self.root.map(|root| unsafe { &(*Self::_leftmost(root).as_ptr()).data })
+    }
+
+    pub fn max(&self) -> Option<&T> {
+        self.root.map(|root| unsafe { &(*Self::_rightmost(root).as_ptr()).data })
+    }
+
+    /// Ascending iterator over the values.
+    pub fn iter(&self) -> RbIter<'_, T> {
+        RbIter {
+            next: self.root.map(Self::_leftmost),
+            remaining: self.size,
+            _marker: PhantomData,
+        }
+    }
+
+    // Puts `new` where `old` hangs from its parent (or the root).
+    fn _replace_child(&mut self, parent: Link<T>, old: NonNull<RbNode<T>>, new: Link<T>) {
+        unsafe {
+            match parent {
+                None => self.root = new,
+                Some(parent) if (*parent.as_ptr()).left == Some(old) => (*parent.as_ptr()).left = new,
+                Some(parent) => (*parent.as_ptr()).right = new,
+            }
+        }
+    }
+
+    fn _left_rotate(&mut self, node: NonNull<RbNode<T>>) {
+        unsafe {
+            let pivot = (*node.as_ptr()).right.expect("Left rotation without a right child.");
+            let inner = (*pivot.as_ptr()).left;
+            let parent = (*node.as_ptr()).parent;
+
+            (*node.as_ptr()).right = inner;
+            if let Some(inner) = inner {
+                (*inner.as_ptr()).parent = Some(node);
+            }
+
+            (*pivot.as_ptr()).parent = parent;
+            self._replace_child(parent, node, Some(pivot));
+
+            (*pivot.as_ptr()).left = Some(node);
+            (*node.as_ptr()).parent = Some(pivot);
+        }
+    }
+
+    fn _right_rotate(&mut self, node: NonNull<RbNode<T>>) {
+        unsafe {
+            let pivot = (*node.as_ptr()).left.expect("Right rotation without a left child.");
+            let inner = (*pivot.as_ptr()).right;
+            let parent = (*node.as_ptr()).parent;
+
+            (*node.as_ptr()).left = inner;
+            if let Some(inner) = inner {
+                (*inner.as_ptr()).parent = Some(node);
+            }
+
+            (*pivot.as_ptr()).parent = parent;
+            self._replace_child(parent, node, Some(pivot));
+
+            (*pivot.as_ptr()).right = Some(node);
+            (*node.as_ptr()).parent = Some(pivot);
+        }
+    }
+
+    /// Inserts `data` into the tree.
+    /// Returns `false` and drops `data` if an equal value is already present.
+    pub fn insert(&mut self, data: T) -> bool {
+        let mut parent = None;
+        let mut cur = self.root;
+        let mut went_left = false;
+        while let Some(node) = cur {
+            let node_ref = unsafe { node.as_ref() };
+            parent = Some(node);
+            cur = match data.cmp(&node_ref.data) {
+                Ordering::Equal => return false,
+                Ordering::Greater => {
+                    went_left = false;
+                    node_ref.right
+                }
+                Ordering::Less => {
+                    went_left = true;
+                    node_ref.left
+                }
+            };
+        }
+
+        let new_node = NonNull::from(Box::leak(Box::new(RbNode::new(data, parent))));
+        unsafe {
+            match parent {
+                None => self.root = Some(new_node),
+                Some(parent) if went_left => (*parent.as_ptr()).left = Some(new_node),
+                Some(parent) => (*parent.as_ptr()).right = Some(new_node),
+            }
+        }
+        self.size += 1;
+        self._insert_fixup(new_node);
+        true
+    }
+
+    // Resolves a red node with a red parent by recoloring up the tree,
+    // finishing with at most two rotations.
+    fn _insert_fixup(&mut self, mut node: NonNull<RbNode<T>>) {
+        unsafe {
+            while let Some(mut parent) = (*node.as_ptr()).parent {
+                if (*parent.as_ptr()).color == Color::Black {
+                    break;
+                }
+                // a red parent is never the root, so the grandparent exists
+                let grandparent = (*parent.as_ptr()).parent.expect("Red root in a red-black tree.");
+
+                if (*grandparent.as_ptr()).left == Some(parent) {
+                    let uncle = (*grandparent.as_ptr()).right;
+                    if color(uncle) == Color::Red {
+                        set_color(Some(parent), Color::Black);
+                        set_color(uncle, Color::Black);
+                        set_color(Some(grandparent), Color::Red);
+                        node = grandparent;
+                        continue;
+                    }
+                    if (*parent.as_ptr()).right == Some(node) {
+                        node = parent;
+                        self._left_rotate(node);
+                        parent = (*node.as_ptr()).parent.expect("Rotated node lost its parent.");
+                    }
+                    set_color(Some(parent), Color::Black);
+                    set_color(Some(grandparent), Color::Red);
+                    self._right_rotate(grandparent);
+                } else {
+                    let uncle = (*grandparent.as_ptr()).left;
+                    if color(uncle) == Color::Red {
+                        set_color(Some(parent), Color::Black);
+                        set_color(uncle, Color::Black);
+                        set_color(Some(grandparent), Color::Red);
+                        node = grandparent;
+                        continue;
+                    }
+                    if (*parent.as_ptr()).left == Some(node) {
+                        node = parent;
+                        self._right_rotate(node);
+                        parent = (*node.as_ptr()).parent.expect("Rotated node lost its parent.");
+                    }
+                    set_color(Some(parent), Color::Black);
+                    set_color(Some(grandparent), Color::Red);
+                    self._left_rotate(grandparent);
+                }
+            }
+        }
+        set_color(self.root, Color::Black);
+    }
+
+    // Replaces the subtree rooted at `old` with the one rooted at `new`.
+    fn _transplant(&mut self, old: NonNull<RbNode<T>>, new: Link<T>) {
+        unsafe {
+            let parent = (*old.as_ptr()).parent;
+            self._replace_child(parent, old, new);
+            if let Some(new) = new {
+                (*new.as_ptr()).parent = parent;
+            }
+        }
+    }
+
+    /// Removes the value equal to `target` and returns it, or `None` if it is not in the tree.
+    pub fn remove(&mut self, target: &T) -> Option<T> {
+        let node = self._find(target)?;
+
+        unsafe {
+            let RbNode { left, right, parent, color: node_color, .. } = *node.as_ptr();
+            let mut removed_color = node_color;
+            // the child that moves into the vacated position, and its new parent
+            let child;
+            let child_parent;
+
+            match (left, right) {
+                (None, right) => {
+                    child = right;
+                    child_parent = parent;
+                    self._transplant(node, right);
+                }
+                (left, None) => {
+                    child = left;
+                    child_parent = parent;
+                    self._transplant(node, left);
+                }
+                (Some(left), Some(right)) => {
+                    // two children: the in-order successor takes the removed node's place
+                    let successor = Self::_leftmost(right);
+                    removed_color = (*successor.as_ptr()).color;
+                    child = (*successor.as_ptr()).right;
+
+                    if successor == right {
+                        child_parent = Some(successor);
+                    } else {
+                        child_parent = (*successor.as_ptr()).parent;
+                        self._transplant(successor, child);
+                        (*successor.as_ptr()).right = Some(right);
+                        (*right.as_ptr()).parent = Some(successor);
+                    }
+
+                    self._transplant(node, Some(successor));
+                    (*successor.as_ptr()).left = Some(left);
+                    (*left.as_ptr()).parent = Some(successor);
+                    (*successor.as_ptr()).color = node_color;
+                }
+            }
+
+            if removed_color == Color::Black {
+                self._remove_fixup(child, child_parent);
+            }
+
+            self.size -= 1;
+            Some(Box::from_raw(node.as_ptr()).data)
+        }
+    }
+
+    // `node` carries an extra black after a removal; push it up the tree or
+    // absorb it with recoloring and at most three rotations.
+    fn _remove_fixup(&mut self, mut node: Link<T>, mut parent: Link<T>) {
+        unsafe {
+            while node != self.root && color(node) == Color::Black {
+                let Some(cur_parent) = parent else {
+                    break;
+                };
+
+                if (*cur_parent.as_ptr()).left == node {
+                    // the doubly black side has a black height of at least one,
+                    // so the sibling exists
+                    let mut sibling = (*cur_parent.as_ptr()).right.expect("Missing sibling in a red-black tree.");
+                    if (*sibling.as_ptr()).color == Color::Red {
+                        set_color(Some(sibling), Color::Black);
+                        set_color(parent, Color::Red);
+                        self._left_rotate(cur_parent);
+                        sibling = (*cur_parent.as_ptr()).right.expect("Missing sibling in a red-black tree.");
+                    }
+
+                    if color((*sibling.as_ptr()).left) == Color::Black
+                        && color((*sibling.as_ptr()).right) == Color::Black
+                    {
+                        set_color(Some(sibling), Color::Red);
+                        node = parent;
+                        parent = (*cur_parent.as_ptr()).parent;
+                        continue;
+                    }
+
+                    if color((*sibling.as_ptr()).right) == Color::Black {
+                        set_color((*sibling.as_ptr()).left, Color::Black);
+                        set_color(Some(sibling), Color::Red);
+                        self._right_rotate(sibling);
+                        sibling = (*cur_parent.as_ptr()).right.expect("Missing sibling in a red-black tree.");
+                    }
+
+                    (*sibling.as_ptr()).color = (*cur_parent.as_ptr()).color;
+                    set_color(parent, Color::Black);
+                    set_color((*sibling.as_ptr()).right, Color::Black);
+                    self._left_rotate(cur_parent);
+                } else {
+                    let mut sibling = (*cur_parent.as_ptr()).left.expect("Missing sibling in a red-black tree.");
+                    if (*sibling.as_ptr()).color == Color::Red {
+                        set_color(Some(sibling), Color::Black);
+                        set_color(parent, Color::Red);
+                        self._right_rotate(cur_parent);
+                        sibling = (*cur_parent.as_ptr()).left.expect("Missing sibling in a red-black tree.");
+                    }
+
+                    if color((*sibling.as_ptr()).left) == Color::Black
+                        && color((*sibling.as_ptr()).right) == Color::Black
+                    {
+                        set_color(Some(sibling), Color::Red);
+                        node = parent;
+                        parent = (*cur_parent.as_ptr()).parent;
+                        continue;
+                    }
+
+                    if color((*sibling.as_ptr()).left) == Color::Black {
+                        set_color((*sibling.as_ptr()).right, Color::Black);
+                        set_color(Some(sibling), Color::Red);
+                        self._left_rotate(sibling);
+                        sibling = (*cur_parent.as_ptr()).left.expect("Missing sibling in a red-black tree.");
+                    }
+
+                    (*sibling.as_ptr()).color = (*cur_parent.as_ptr()).color;
+                    set_color(parent, Color::Black);
+                    set_color((*sibling.as_ptr()).left, Color::Black);
+                    self._right_rotate(cur_parent);
+                }
+                node = self.root;
+                break;
+            }
+        }
+        set_color(node, Color::Black);
+    }
+
+    pub fn clear(&mut self) {
+        let mut stack: Vec<NonNull<RbNode<T>>> = self.root.take().into_iter().collect();
+        while let Some(node) = stack.pop() {
+            let node = unsafe { Box::from_raw(node.as_ptr()) };
+            stack.extend(node.left);
+            stack.extend(node.right);
+        }
+        self.size = 0;
+    }
+
+    /// Checks the red-black invariants: a black root, no red node with a red
+    /// child, the same number of black nodes on every root-to-leaf path, plus
+    /// ordering, parent links and the cached size.
+    /// Returns the black height of the tree.
+    pub fn validate(&self) -> Result<usize, String> {
+        if color(self.root) == Color::Red {
+            return Err("root is red".to_string());
+        }
+        let (black_height, count) = Self::_validate_node(self.root, None, None, None)?;
+        if count != self.size {
+            return Err(format!("size is {} but the tree holds {} nodes", self.size, count));
+        }
+        Ok(black_height)
+    }
+
+    // Returns the black height and node count of the subtree.
+    fn _validate_node(
+        link: Link<T>,
+        parent: Link<T>,
+        low: Option<&T>,
+        high: Option<&T>,
+    ) -> Result<(usize, usize), String> {
+        let Some(node) = link else {
+            return Ok((1, 0));
+        };
+        let node_ref = unsafe { &*node.as_ptr() };
+
+        if node_ref.parent != parent {
+            return Err("broken parent link".to_string());
+        }
+        if low.is_some_and(|low| node_ref.data <= *low) || high.is_some_and(|high| node_ref.data >= *high) {
+            return Err("values out of order".to_string());
+        }
+        if node_ref.color == Color::Red
+            && (color(node_ref.left) == Color::Red || color(node_ref.right) == Color::Red)
+        {
+            return Err("red node with a red child".to_string());
+        }
+
+        let (left_black, left_count) = Self::_validate_node(node_ref.left, link, low, Some(&node_ref.data))?;
+        let (right_black, right_count) = Self::_validate_node(node_ref.right, link, Some(&node_ref.data), high)?;
+        if left_black != right_black {
+            return Err("paths with different black heights".to_string());
+        }
+
+        let own = usize::from(node_ref.color == Color::Black);
+        Ok((left_black + own, left_count + right_count + 1))
+    }
+}
+
+impl<T> Default for RbTree<T>
+where
+    T: Ord,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for RbTree<T>
+where
+    T: Ord,
+{
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+pub struct RbIter<'a, T>
+where
+    T: Ord,
+{
+    next: Link<T>,
+    remaining: usize,
+    _marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for RbIter<'a, T>
+where
+    T: Ord,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next?;
+        let node_ref = unsafe { &*node.as_ptr() };
+
+        // in-order successor: leftmost of the right subtree, or the first
+        // ancestor reached from its left side
+        self.next = match node_ref.right {
+            Some(right) => Some(RbTree::_leftmost(right)),
+            None => {
+                let mut child = node;
+                let mut parent = node_ref.parent;
+                while let Some(up) = parent {
+                    if unsafe { up.as_ref().left } == Some(child) {
+                        break;
+                    }
+                    child = up;
+                    parent = unsafe { up.as_ref().parent };
+                }
+                parent
+            }
+        };
+
+        self.remaining -= 1;
+        Some(&node_ref.data)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T: Ord> IntoIterator for &'a RbTree<T> {
+    type Item = &'a T;
+    type IntoIter = RbIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RbTree;
+
+    // deterministic shuffle of 0..n
+    fn scrambled(n: u64) -> Vec<u64> {
+        (0..n).map(|x| x * 7_919 % n).collect()
+    }
+
+    #[test]
+    fn insert_keeps_invariants() {
+        let mut tree = RbTree::new();
+        for x in scrambled(1_000) {
+            assert!(tree.insert(x));
+            assert!(tree.validate().is_ok());
+        }
+        assert!(!tree.insert(500));
+        assert_eq!(tree.len(), 1_000);
+        assert_eq!((tree.min(), tree.max()), (Some(&0), Some(&999)));
+        assert!(tree.search(&123));
+        assert!(!tree.search(&1_000));
+        assert!(tree.iter().copied().eq(0..1_000));
+    }
+
+    #[test]
+    fn sorted_insert_stays_shallow() {
+        let mut tree = RbTree::new();
+        for x in 0..4_096 {
+            tree.insert(x);
+        }
+        // black height bounds the depth to twice itself
+        let black_height = tree.validate().unwrap();
+        assert!(black_height <= 13);
+    }
+
+    #[test]
+    fn remove_keeps_invariants() {
+        let mut tree = RbTree::new();
+        for x in scrambled(500) {
+            tree.insert(x);
+        }
+        for x in scrambled(500).into_iter().filter(|x| x % 4 != 0) {
+            assert_eq!(tree.remove(&x), Some(x));
+            assert_eq!(tree.remove(&x), None);
+            if let Err(violation) = tree.validate() {
+                panic!("after removing {}: {}", x, violation);
+            }
+        }
+        assert_eq!(tree.len(), 125);
+        assert!(tree.iter().copied().eq((0..500).step_by(4)));
+
+        for x in (0..500).step_by(4) {
+            tree.remove(&x);
+        }
+        assert!(tree.is_empty());
+        assert_eq!(tree.min(), None);
+        assert_eq!(tree.validate(), Ok(1));
+    }
+}
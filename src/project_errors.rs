@@ -1,36 +1,58 @@
 use std::{error, fmt};
 
-#[derive(Debug, Clone)]
-pub struct OutOfIndexError;
-impl error::Error for OutOfIndexError {}
-
-impl fmt::Display for OutOfIndexError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Index out of the range.")
-    }
+/// Errors reported by the collections in this crate.
+///
+/// Every variant records the operation that failed, so callers can match on
+/// the failure without downcasting and still print a useful message.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CollectionError {
+    /// `index` was outside the valid range for a collection of length `len`.
+    IndexOutOfRange {
+        operation: &'static str,
+        index: usize,
+        len: usize,
+    },
+    /// The operation needs at least one element.
+    Empty { operation: &'static str },
+    /// A handle that is stale or belongs to another collection.
+    InvalidHandle { operation: &'static str },
+    /// A key was asked to decrease but the new key is greater.
+    KeyIncrease { operation: &'static str },
+    /// The operation does not allow negative edge weights.
+    NegativeWeight { operation: &'static str },
+    /// A cycle of negative total weight makes shortest paths undefined.
+    NegativeCycle { operation: &'static str },
+    /// A path length does not fit in the weight type.
+    WeightOverflow { operation: &'static str },
 }
 
+impl error::Error for CollectionError {}
 
-// 
-#[derive(Debug, Clone)]
-pub struct NotValidIndexError;
-impl error::Error for NotValidIndexError {}
-
-impl fmt::Display for NotValidIndexError {
-  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    write!(f, "Not a valid index.")
-  }
-}
-
-#[derive(Debug, Clone)]
-pub struct EmptyList;
-
-impl error::Error for EmptyList {}
-
-impl fmt::Display for EmptyList {
-  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    write!(f, "Empty list.")
-  }
+impl fmt::Display for CollectionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CollectionError::IndexOutOfRange { operation, index, len } => {
+                write!(f, "{}: index {} out of the range for length {}.", operation, index, len)
+            }
+            CollectionError::Empty { operation } => write!(f, "{}: empty list.", operation),
+            CollectionError::InvalidHandle { operation } => {
+                write!(f, "{}: handle does not refer to an element of this collection.", operation)
+            }
+            CollectionError::KeyIncrease { operation } => {
+                write!(f, "{}: new key is greater than the current key.", operation)
+            }
+            CollectionError::NegativeWeight { operation } => {
+                write!(f, "{}: negative edge weight.", operation)
+            }
+            CollectionError::NegativeCycle { operation } => {
+                write!(f, "{}: the graph has a negative cycle.", operation)
+            }
+            CollectionError::WeightOverflow { operation } => {
+                write!(f, "{}: path length overflows the weight type.", operation)
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone)]